@@ -0,0 +1,271 @@
+use std::{fmt, panic::Location};
+
+use parking_lot::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+#[cfg(debug_assertions)]
+use {
+	crate::util::ptr::addr_of_ptr,
+	std::sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering::*},
+};
+
+// === BorrowTracker === //
+
+#[cfg(debug_assertions)]
+const MAX_TRACKED_HOLDERS: usize = 8;
+
+// Must be a power of two.
+#[cfg(debug_assertions)]
+const SLOT_COUNT: usize = 64;
+
+pub type BorrowLocation = &'static Location<'static>;
+
+#[derive(Debug, Clone)]
+pub struct BorrowConflict {
+	pub mutably: bool,
+	pub recent_holders: Vec<BorrowLocation>,
+}
+
+// Borrows are tracked on every universe access so recording them must never block. Instead of a
+// map, each lock hashes to a slot of atomics which it overwrites when it gets acquired. Slots are
+// only read back when reporting a conflict.
+//
+// Guards are released without notifying the tracker so a slot only knows which borrows were most
+// recently acquired since the lock was last observed unlocked. Shared borrows which have since been
+// released are still reported and, since slots only keep the last `MAX_TRACKED_HOLDERS` borrows,
+// a long-lived holder can be evicted by short-lived ones. Locks sharing a slot also evict one
+// another's records and concurrent writers may tear them. Conflicts should therefore only be
+// taken as a list of recent, possible holders.
+pub struct BorrowTracker {
+	#[cfg(debug_assertions)]
+	slots: Box<[BorrowSlot]>,
+}
+
+impl fmt::Debug for BorrowTracker {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("BorrowTracker").finish_non_exhaustive()
+	}
+}
+
+// (only derivable in release builds)
+#[allow(clippy::derivable_impls)]
+impl Default for BorrowTracker {
+	fn default() -> Self {
+		Self {
+			#[cfg(debug_assertions)]
+			slots: (0..SLOT_COUNT).map(|_| BorrowSlot::default()).collect(),
+		}
+	}
+}
+
+#[cfg(debug_assertions)]
+#[derive(Default)]
+struct BorrowSlot {
+	addr: AtomicUsize,
+	mutably: AtomicBool,
+	pushed: AtomicUsize,
+	holders: [AtomicPtr<Location<'static>>; MAX_TRACKED_HOLDERS],
+}
+
+#[cfg(debug_assertions)]
+impl BorrowSlot {
+	fn record(&self, addr: usize, mutably: bool, was_unlocked: bool, location: BorrowLocation) {
+		// Guards are released without notifying the tracker so we lazily clear out stale holders
+		// the next time we observe the lock in an unlocked state.
+		let is_stale = was_unlocked
+			|| mutably
+			|| self.mutably.load(Relaxed)
+			|| self.addr.load(Relaxed) != addr;
+
+		if is_stale {
+			// (prevents readers from associating the old holders with the new address)
+			self.addr.store(0, Release);
+			self.pushed.store(0, Relaxed);
+		}
+
+		let index = self.pushed.fetch_add(1, Relaxed) % MAX_TRACKED_HOLDERS;
+		self.holders[index].store(location as *const _ as *mut _, Relaxed);
+		self.mutably.store(mutably, Relaxed);
+		self.addr.store(addr, Release);
+	}
+
+	fn holders(&self, addr: usize, mutably: bool) -> Vec<BorrowLocation> {
+		if self.addr.load(Acquire) != addr || self.mutably.load(Relaxed) != mutably {
+			return Vec::new();
+		}
+
+		// Holders are returned from oldest to newest.
+		let pushed = self.pushed.load(Relaxed);
+		let start = pushed.saturating_sub(MAX_TRACKED_HOLDERS);
+		let holders = (start..pushed)
+			.filter_map(|i| {
+				let holder = self.holders[i % MAX_TRACKED_HOLDERS].load(Relaxed);
+
+				// Safety: we only ever store `&'static Location`s in here.
+				unsafe { holder.as_ref() }
+			})
+			.collect();
+
+		// If the slot was taken over while we were reading it, our holders may belong to another
+		// lock.
+		if self.addr.load(Acquire) != addr {
+			return Vec::new();
+		}
+
+		holders
+	}
+
+	fn forget(&self, addr: usize) {
+		let _ = self.addr.compare_exchange(addr, 0, AcqRel, Relaxed);
+	}
+}
+
+impl BorrowTracker {
+	#[track_caller]
+	pub fn try_read<'a, T: ?Sized>(
+		&self,
+		lock: &'a RwLock<T>,
+	) -> Result<RwLockReadGuard<'a, T>, BorrowConflict> {
+		#[cfg(debug_assertions)]
+		{
+			self.track(
+				addr_of_ptr(lock),
+				false,
+				|| lock.is_locked().then(|| lock.is_locked_exclusive()),
+				|| lock.try_read(),
+			)
+		}
+
+		#[cfg(not(debug_assertions))]
+		{
+			lock.try_read().ok_or(BorrowConflict {
+				mutably: true,
+				recent_holders: Vec::new(),
+			})
+		}
+	}
+
+	#[track_caller]
+	pub fn try_write<'a, T: ?Sized>(
+		&self,
+		lock: &'a RwLock<T>,
+	) -> Result<RwLockWriteGuard<'a, T>, BorrowConflict> {
+		#[cfg(debug_assertions)]
+		{
+			self.track(
+				addr_of_ptr(lock),
+				true,
+				|| lock.is_locked().then(|| lock.is_locked_exclusive()),
+				|| lock.try_write(),
+			)
+		}
+
+		#[cfg(not(debug_assertions))]
+		{
+			lock.try_write().ok_or_else(|| BorrowConflict {
+				mutably: lock.is_locked_exclusive(),
+				recent_holders: Vec::new(),
+			})
+		}
+	}
+
+	#[track_caller]
+	pub fn try_lock<'a, T: ?Sized>(
+		&self,
+		lock: &'a Mutex<T>,
+	) -> Result<MutexGuard<'a, T>, BorrowConflict> {
+		#[cfg(debug_assertions)]
+		{
+			self.track(
+				addr_of_ptr(lock),
+				true,
+				|| lock.is_locked().then_some(true),
+				|| lock.try_lock(),
+			)
+		}
+
+		#[cfg(not(debug_assertions))]
+		{
+			lock.try_lock().ok_or(BorrowConflict {
+				mutably: true,
+				recent_holders: Vec::new(),
+			})
+		}
+	}
+
+	pub fn forget<T: ?Sized>(&self, lock: *const T) {
+		#[cfg(debug_assertions)]
+		self.slot(addr_of_ptr(lock)).forget(addr_of_ptr(lock));
+
+		#[cfg(not(debug_assertions))]
+		let _ = lock;
+	}
+
+	#[cfg(debug_assertions)]
+	fn slot(&self, addr: usize) -> &BorrowSlot {
+		// Fibonacci hashing spreads out the heavily aligned lock addresses.
+		let hash = (addr as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+		&self.slots[(hash >> (64 - SLOT_COUNT.trailing_zeros())) as usize]
+	}
+
+	#[cfg(debug_assertions)]
+	#[track_caller]
+	fn track<G>(
+		&self,
+		addr: usize,
+		mutably: bool,
+		lock_state: impl Fn() -> Option<bool>,
+		acquire: impl FnOnce() -> Option<G>,
+	) -> Result<G, BorrowConflict> {
+		let was_unlocked = lock_state().is_none();
+
+		match acquire() {
+			Some(guard) => {
+				self.slot(addr)
+					.record(addr, mutably, was_unlocked, Location::caller());
+
+				Ok(guard)
+			}
+			None => {
+				let slot = self.slot(addr);
+				let mutably = lock_state().unwrap_or_else(|| slot.mutably.load(Relaxed));
+
+				// If the lock was acquired without going through the tracker, our records are
+				// describing a borrow which no longer exists.
+				Err(BorrowConflict {
+					mutably,
+					recent_holders: slot.holders(addr, mutably),
+				})
+			}
+		}
+	}
+}
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reports_conflicting_holders() {
+		let tracker = BorrowTracker::default();
+		let lock = RwLock::new(0);
+
+		let first = tracker.try_read(&lock).unwrap();
+		let second = tracker.try_read(&lock).unwrap();
+		let conflict = tracker.try_write(&lock).unwrap_err();
+
+		assert!(!conflict.mutably);
+		assert_eq!(conflict.recent_holders.len(), 2);
+		assert!(conflict
+			.recent_holders
+			.iter()
+			.all(|loc| loc.file() == file!()));
+
+		drop((first, second));
+
+		let _guard = tracker.try_write(&lock).unwrap();
+		let conflict = tracker.try_read(&lock).unwrap_err();
+
+		assert!(conflict.mutably);
+		assert_eq!(conflict.recent_holders.len(), 1);
+	}
+}
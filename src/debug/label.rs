@@ -1,6 +1,9 @@
 use std::{borrow::Cow, fmt};
 
-pub type ReifiedDebugLabel = Option<Cow<'static, str>>;
+use fnv::{FnvHashMap, FnvHashSet};
+use parking_lot::Mutex;
+
+pub type ReifiedDebugLabel = Option<ReifiedLabel>;
 
 pub trait DebugLabel: Sized {
 	fn reify(self) -> ReifiedDebugLabel;
@@ -18,27 +21,323 @@ pub const NO_LABEL: Option<&'static str> = None;
 
 impl DebugLabel for String {
 	fn reify(self) -> ReifiedDebugLabel {
-		Some(Cow::Owned(self))
+		Some(ReifiedLabel::Str(Cow::Owned(self)))
+	}
+}
+
+impl DebugLabel for Cow<'static, str> {
+	fn reify(self) -> ReifiedDebugLabel {
+		Some(ReifiedLabel::Str(self))
 	}
 }
 
 impl DebugLabel for &'static str {
 	fn reify(self) -> ReifiedDebugLabel {
-		Some(Cow::Borrowed(self))
+		Some(ReifiedLabel::Str(Cow::Borrowed(self)))
 	}
 }
 
 impl DebugLabel for fmt::Arguments<'_> {
 	fn reify(self) -> ReifiedDebugLabel {
 		match self.as_str() {
-			Some(static_str) => Some(Cow::Borrowed(static_str)),
-			None => Some(Cow::Owned(format!("{self}"))),
+			Some(static_str) => static_str.reify(),
+			None => format!("{self}").reify(),
 		}
 	}
 }
 
+impl DebugLabel for LazyLabel {
+	fn reify(self) -> ReifiedDebugLabel {
+		Some(ReifiedLabel::Lazy(self))
+	}
+}
+
+impl DebugLabel for ReifiedLabel {
+	fn reify(self) -> ReifiedDebugLabel {
+		Some(self)
+	}
+}
+
 impl<T: DebugLabel> DebugLabel for Option<T> {
 	fn reify(self) -> ReifiedDebugLabel {
 		self.and_then(DebugLabel::reify)
 	}
 }
+
+// === ReifiedLabel === //
+
+#[derive(Debug, Clone)]
+pub enum ReifiedLabel {
+	Str(Cow<'static, str>),
+	Lazy(LazyLabel),
+}
+
+impl ReifiedLabel {
+	// Lazy labels are grouped by their template.
+	pub fn template(&self) -> &str {
+		match self {
+			Self::Str(name) => name,
+			Self::Lazy(lazy) => lazy.template(),
+		}
+	}
+
+	pub fn to_str(&self) -> Cow<'_, str> {
+		match self {
+			Self::Str(name) => Cow::Borrowed(name),
+			Self::Lazy(lazy) => Cow::Owned(lazy.to_string()),
+		}
+	}
+}
+
+impl fmt::Display for ReifiedLabel {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Str(name) => f.write_str(name),
+			Self::Lazy(lazy) => fmt::Display::fmt(lazy, f),
+		}
+	}
+}
+
+// === LazyLabel === //
+
+pub const MAX_LAZY_LABEL_ARGS: usize = 3;
+
+// Templates only support `{}` placeholders and the `{{`/`}}` escapes.
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct LazyLabel {
+	template: &'static str,
+	args: [Option<LabelArg>; MAX_LAZY_LABEL_ARGS],
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum LabelArg {
+	Int(i64),
+	UInt(u64),
+	Bool(bool),
+	Char(char),
+	Str(&'static str),
+}
+
+impl LazyLabel {
+	pub fn new(template: &'static str, args: &[LabelArg]) -> Self {
+		assert!(
+			args.len() <= MAX_LAZY_LABEL_ARGS,
+			"`LazyLabel`s can take at most {MAX_LAZY_LABEL_ARGS} arguments but {} were provided.",
+			args.len(),
+		);
+
+		debug_assert_eq!(
+			Self::count_placeholders(template),
+			args.len(),
+			"Mismatched argument count for the label template {template:?}.",
+		);
+
+		let mut stored = [None; MAX_LAZY_LABEL_ARGS];
+		for (slot, arg) in stored.iter_mut().zip(args) {
+			*slot = Some(*arg);
+		}
+
+		Self {
+			template,
+			args: stored,
+		}
+	}
+
+	pub fn template(&self) -> &'static str {
+		self.template
+	}
+
+	pub fn args(&self) -> impl Iterator<Item = LabelArg> + '_ {
+		self.args.iter().map_while(|arg| *arg)
+	}
+
+	fn count_placeholders(template: &str) -> usize {
+		let mut count = 0;
+		let mut chars = template.chars().peekable();
+
+		while let Some(char) = chars.next() {
+			match (char, chars.peek()) {
+				('{', Some('{')) | ('}', Some('}')) => {
+					chars.next();
+				}
+				('{', Some('}')) => {
+					chars.next();
+					count += 1;
+				}
+				_ => {}
+			}
+		}
+
+		count
+	}
+}
+
+impl fmt::Display for LazyLabel {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut args = self.args();
+		let mut remaining = self.template;
+
+		while let Some(index) = remaining.find(['{', '}']) {
+			f.write_str(&remaining[..index])?;
+
+			let escape = &remaining[index..];
+			if escape.starts_with("{{") || escape.starts_with("}}") {
+				f.write_str(&escape[..1])?;
+			} else if escape.starts_with("{}") {
+				match args.next() {
+					Some(arg) => fmt::Display::fmt(&arg, f)?,
+					None => f.write_str("{}")?,
+				}
+			} else {
+				// (a lone brace is printed as-is)
+				f.write_str(&escape[..1])?;
+				remaining = &escape[1..];
+				continue;
+			}
+
+			remaining = &escape[2..];
+		}
+
+		f.write_str(remaining)
+	}
+}
+
+impl fmt::Display for LabelArg {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Int(value) => fmt::Display::fmt(value, f),
+			Self::UInt(value) => fmt::Display::fmt(value, f),
+			Self::Bool(value) => fmt::Display::fmt(value, f),
+			Self::Char(value) => fmt::Display::fmt(value, f),
+			Self::Str(value) => f.write_str(value),
+		}
+	}
+}
+
+macro_rules! impl_label_arg_from {
+	($($variant:ident($target:ty) <= $($ty:ty),*;)*) => {$($(
+		impl From<$ty> for LabelArg {
+			fn from(value: $ty) -> Self {
+				Self::$variant(value as $target)
+			}
+		}
+	)*)*};
+}
+
+impl_label_arg_from! {
+	Int(i64) <= i8, i16, i32, i64, isize;
+	UInt(u64) <= u8, u16, u32, u64, usize;
+}
+
+impl From<bool> for LabelArg {
+	fn from(value: bool) -> Self {
+		Self::Bool(value)
+	}
+}
+
+impl From<char> for LabelArg {
+	fn from(value: char) -> Self {
+		Self::Char(value)
+	}
+}
+
+impl From<&'static str> for LabelArg {
+	fn from(value: &'static str) -> Self {
+		Self::Str(value)
+	}
+}
+
+#[macro_export]
+macro_rules! lazy_label {
+	($template:literal $(, $arg:expr)* $(,)?) => {{
+		const _: () = assert!(
+			<[()]>::len(&[$($crate::lazy_label!(@unit $arg)),*])
+				<= $crate::debug::label::MAX_LAZY_LABEL_ARGS,
+			"Too many arguments were passed to `lazy_label!`.",
+		);
+
+		$crate::debug::label::LazyLabel::new(
+			$template,
+			&[$($crate::debug::label::LabelArg::from($arg)),*],
+		)
+	}};
+	(@unit $arg:expr) => {
+		()
+	};
+}
+
+pub use lazy_label;
+
+// === Interning === //
+
+static INTERNED_LABELS: Mutex<Option<FnvHashSet<&'static str>>> = Mutex::new(None);
+static INTERNED_LAZY_LABELS: Mutex<Option<FnvHashMap<LazyLabel, &'static str>>> = Mutex::new(None);
+
+// Interned labels are never freed so this should only be used for names drawn from a small set.
+pub fn intern_label(label: &str) -> &'static str {
+	let mut interned = INTERNED_LABELS.lock();
+	let interned = interned.get_or_insert_with(FnvHashSet::default);
+
+	match interned.get(label) {
+		Some(label) => label,
+		None => {
+			let label = Box::leak(Box::<str>::from(label));
+			interned.insert(label);
+			label
+		}
+	}
+}
+
+// Lazy labels are interned by their template and arguments so that they're only ever formatted
+// once.
+pub fn intern_lazy_label(label: LazyLabel) -> &'static str {
+	let mut interned = INTERNED_LAZY_LABELS.lock();
+	let interned = interned.get_or_insert_with(FnvHashMap::default);
+
+	interned
+		.entry(label)
+		.or_insert_with(|| intern_label(&label.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn counts_placeholders() {
+		assert_eq!(LazyLabel::count_placeholders("no placeholders"), 0);
+		assert_eq!(LazyLabel::count_placeholders("{} and {}"), 2);
+		assert_eq!(LazyLabel::count_placeholders("{{}} and {{{}}}"), 1);
+		assert_eq!(LazyLabel::count_placeholders("lone { and }"), 0);
+	}
+
+	#[test]
+	fn formats_templates() {
+		assert_eq!(
+			lazy_label!("zombie {} of {}", 3u32, "wave").to_string(),
+			"zombie 3 of wave"
+		);
+		assert_eq!(lazy_label!("{}{}{}", -1, true, 'c').to_string(), "-1truec");
+		assert_eq!(
+			lazy_label!("{{escaped}} {{{}}}", 4).to_string(),
+			"{escaped} {4}"
+		);
+		assert_eq!(lazy_label!("lone { and }").to_string(), "lone { and }");
+	}
+
+	#[test]
+	fn interns_labels() {
+		let label = intern_label(&String::from("interned"));
+		assert_eq!(label, "interned");
+		assert!(std::ptr::eq(label, intern_label("interned")));
+
+		let lazy = intern_lazy_label(lazy_label!("interned {}", 1));
+		assert_eq!(lazy, "interned 1");
+		assert!(std::ptr::eq(
+			lazy,
+			intern_lazy_label(lazy_label!("interned {}", 1))
+		));
+		assert!(std::ptr::eq(lazy, intern_label("interned 1")));
+		assert_ne!(intern_lazy_label(lazy_label!("interned {}", 2)), lazy);
+	}
+}
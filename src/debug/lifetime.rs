@@ -1,28 +1,126 @@
-use std::{borrow::Borrow, cmp::Ordering, fmt, hash, num::NonZeroU64};
-
-use super::label::{DebugLabel, ReifiedDebugLabel};
+use std::{
+	backtrace::Backtrace,
+	borrow::{Borrow, Cow},
+	cmp::Ordering,
+	collections::VecDeque,
+	fmt, hash, mem,
+	num::NonZeroU64,
+	sync::{
+		atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering::*},
+		Arc,
+	},
+};
+
+use fnv::FnvHashMap;
+
+use super::{
+	label::{DebugLabel, ReifiedDebugLabel},
+	violation::{report_violation, DebugViolation},
+};
 
 // === Global === //
 
 type LifetimeSlot = &'static SlotData;
 
+// Liveness and dependency checks happen on every debug-mode storage access so they must never
+// block. Names, meanwhile, are only touched while creating, destroying, formatting, and reporting
+// on lifetimes so they can stay behind a lock.
 #[derive(Debug)]
-struct SlotData(parking_lot::Mutex<SlotDataInner>);
+struct SlotData {
+	gen: AtomicU64,
+	deps: AtomicU64,
+	info: parking_lot::Mutex<SlotInfo>,
+}
 
 #[derive(Debug)]
-struct SlotDataInner {
-	gen: NonZeroU64,
-	deps: usize,
+struct SlotInfo {
+	in_use: bool,
 	curr_name: ReifiedDebugLabel,
-	dead_name: ReifiedDebugLabel,
+	next_holder: u64,
+	holders: FnvHashMap<NonZeroU64, ReifiedDebugLabel>,
+	curr_created_at: Option<Arc<Backtrace>>,
+	// Ordered from oldest to newest. Generations are consecutive and the newest one always directly
+	// precedes the slot's current generation.
+	history: VecDeque<DeadGeneration>,
+}
+
+#[derive(Debug)]
+struct DeadGeneration {
+	gen: u64,
+	name: ReifiedDebugLabel,
+	created_at: Option<Arc<Backtrace>>,
+	destroyed_at: Option<Arc<Backtrace>>,
+}
+
+impl SlotInfo {
+	fn dead_generation(&self, local_gen: u64, curr_gen: u64) -> Option<&DeadGeneration> {
+		let age = usize::try_from(curr_gen.checked_sub(local_gen)?.checked_sub(1)?).ok()?;
+		let index = self.history.len().checked_sub(age + 1)?;
+		let dead = &self.history[index];
+		debug_assert_eq!(dead.gen, local_gen);
+
+		Some(dead)
+	}
+}
+
+// The dependency word packs the lower 32 bits of the generation to which the dependencies belong
+// into its upper half and the dependency count into its lower half. This lets us atomically
+// reject dependency changes made through stale lifetimes.
+const DEP_COUNT_MASK: u64 = u32::MAX as u64;
+
+fn dep_tag(gen: u64) -> u64 {
+	gen << 32
+}
+
+// Capturing backtraces is extremely slow so this is opt-in.
+static CAPTURE_BACKTRACES: AtomicBool = AtomicBool::new(false);
+
+pub fn set_capture_backtraces(enabled: bool) {
+	CAPTURE_BACKTRACES.store(enabled, Relaxed);
+}
+
+pub fn captures_backtraces() -> bool {
+	CAPTURE_BACKTRACES.load(Relaxed)
+}
+
+fn capture_backtrace() -> Option<Arc<Backtrace>> {
+	captures_backtraces().then(|| Arc::new(Backtrace::force_capture()))
+}
+
+// Registering a holder locks the lifetime's slot every time a named `Dependent` is created or
+// dropped. Storages and event queues create a named dependent for every component and event they
+// hold so this is opt-in as well.
+static TRACK_DEPENDENT_HOLDERS: AtomicBool = AtomicBool::new(false);
+
+pub fn set_track_dependent_holders(enabled: bool) {
+	TRACK_DEPENDENT_HOLDERS.store(enabled, Relaxed);
+}
+
+pub fn tracks_dependent_holders() -> bool {
+	TRACK_DEPENDENT_HOLDERS.load(Relaxed)
+}
+
+// The number of dead generations whose names (and backtraces) each slot remembers. Stale handles
+// older than this print as "<name unavailable>".
+static NAME_HISTORY_DEPTH: AtomicUsize = AtomicUsize::new(4);
+
+pub fn set_name_history_depth(depth: usize) {
+	// We always keep the most recent generation around so that double-destroys and the like can
+	// be reported properly.
+	NAME_HISTORY_DEPTH.store(depth.max(1), Relaxed);
+}
+
+pub fn name_history_depth() -> usize {
+	NAME_HISTORY_DEPTH.load(Relaxed)
 }
 
 mod db {
-	use std::{cell::RefCell, num::NonZeroU64};
+	use std::{cell::RefCell, collections::VecDeque, sync::atomic::AtomicU64};
 
+	use fnv::FnvHashMap;
 	use parking_lot::Mutex;
 
-	use super::{LifetimeSlot, SlotData, SlotDataInner};
+	use super::{dep_tag, LifetimeSlot, SlotData, SlotInfo};
 
 	use crate::util::pool::{GlobalPool, LocalPool};
 
@@ -30,6 +128,10 @@ mod db {
 
 	static GLOBAL_POOL: GlobalPool<LifetimeSlot> = GlobalPool::new();
 
+	// Slots are never deallocated so we keep track of every block we've ever leaked to let us
+	// enumerate lifetimes while generating reports.
+	static BLOCKS: Mutex<Vec<&'static [SlotData]>> = Mutex::new(Vec::new());
+
 	thread_local! {
 		static LOCAL_POOL: RefCell<LocalPool<LifetimeSlot>> = const { RefCell::new(LocalPool::new()) };
 	}
@@ -40,22 +142,34 @@ mod db {
 
 			local_pool.acquire(&GLOBAL_POOL, || {
 				let values = (0..POOL_BLOCK_SIZE)
-					.map(|_| {
-						SlotData(Mutex::new(SlotDataInner {
-							gen: NonZeroU64::new(1).unwrap(),
-							deps: 0,
+					.map(|_| SlotData {
+						gen: AtomicU64::new(1),
+						deps: AtomicU64::new(dep_tag(1)),
+						info: Mutex::new(SlotInfo {
+							in_use: false,
 							curr_name: None,
-							dead_name: None,
-						}))
+							next_holder: 1,
+							holders: FnvHashMap::default(),
+							curr_created_at: None,
+							history: VecDeque::new(),
+						}),
 					})
 					.collect::<Vec<_>>()
 					.leak();
 
+				BLOCKS.lock().push(values);
+
 				values.iter().collect()
 			})
 		})
 	}
 
+	pub(super) fn all_slots() -> impl Iterator<Item = LifetimeSlot> {
+		let blocks = BLOCKS.lock().clone();
+
+		blocks.into_iter().flatten()
+	}
+
 	pub(super) fn free_slot(slot: LifetimeSlot) {
 		LOCAL_POOL.with(|local_pool| {
 			local_pool
@@ -91,19 +205,26 @@ impl PartialEq for Lifetime {
 impl Lifetime {
 	pub fn new<L: DebugLabel>(name: L) -> Self {
 		let curr_name = name.reify();
+		let created_at = capture_backtrace();
 
-		let slot = db::alloc_slot();
-		let mut slot_guard = slot.0.lock();
-		slot_guard.curr_name = curr_name;
+		stats::record_alloc(&curr_name);
 
-		Self {
-			slot,
-			gen: slot_guard.gen,
+		let slot = db::alloc_slot();
+		{
+			let mut info = slot.info.lock();
+			info.in_use = true;
+			info.curr_name = curr_name;
+			info.curr_created_at = created_at;
 		}
+
+		// We own the slot until it gets destroyed so nothing can change its generation.
+		let gen = NonZeroU64::new(slot.gen.load(Acquire)).unwrap();
+
+		Self { slot, gen }
 	}
 
 	pub fn is_alive(self) -> bool {
-		self.gen == self.slot.0.lock().gen
+		self.gen.get() == self.slot.gen.load(Acquire)
 	}
 
 	pub fn is_condemned(self) -> bool {
@@ -111,73 +232,138 @@ impl Lifetime {
 	}
 
 	pub fn try_inc_dep(self) -> bool {
-		let mut slot_guard = self.slot.0.lock();
-
-		if slot_guard.gen != self.gen {
-			return false;
+		let tag = dep_tag(self.gen.get());
+		let mut word = self.slot.deps.load(Acquire);
+
+		loop {
+			if word & !DEP_COUNT_MASK != tag {
+				return false;
+			}
+
+			if word & DEP_COUNT_MASK == DEP_COUNT_MASK {
+				panic!(
+					"Marked too many dependencies on `Lifetime` with name {:?}.",
+					self.debug_name(),
+				);
+			}
+
+			match self
+				.slot
+				.deps
+				.compare_exchange_weak(word, word + 1, AcqRel, Acquire)
+			{
+				Ok(_) => return true,
+				Err(actual) => word = actual,
+			}
 		}
-
-		slot_guard.deps = slot_guard.deps.checked_add(1).unwrap_or_else(|| {
-			panic!(
-				"Marked too many dependencies on `Lifetime` with name {:?}.",
-				slot_guard.curr_name,
-			)
-		});
-
-		true
 	}
 
 	pub fn inc_dep(self) {
 		if !self.try_inc_dep() {
-			log::error!(
-				"Attempted to increment the dependency counter on the dead lifetime {:?}.",
-				self
-			);
+			report_violation(DebugViolation::use_after_free(
+				self,
+				format!(
+					"Attempted to increment the dependency counter on the dead lifetime {:?}.",
+					self
+				),
+			));
 		}
 	}
 
 	pub fn dec_dep(self) {
-		let mut slot_guard = self.slot.0.lock();
-
-		if slot_guard.gen != self.gen {
-			// (ignored to reduce spam a bit)
-			return;
+		let tag = dep_tag(self.gen.get());
+		let mut word = self.slot.deps.load(Acquire);
+
+		loop {
+			if word & !DEP_COUNT_MASK != tag {
+				// (ignored to reduce spam a bit)
+				return;
+			}
+
+			if word & DEP_COUNT_MASK == 0 {
+				panic!(
+					"Decremented dependency counter of `Lifetime` with name {:?} more times than it was incremented.",
+					self.debug_name(),
+				);
+			}
+
+			match self
+				.slot
+				.deps
+				.compare_exchange_weak(word, word - 1, AcqRel, Acquire)
+			{
+				Ok(_) => return,
+				Err(actual) => word = actual,
+			}
 		}
-
-		slot_guard.deps = slot_guard.deps.checked_sub(1).unwrap_or_else(|| {
-			panic!(
-				"Decremented dependency counter of `Lifetime` with name {:?} more times than it was incremented.",
-				slot_guard.curr_name,
-			)
-		});
 	}
 
 	pub fn try_destroy(self) -> bool {
-		let mut slot_guard = self.slot.0.lock();
+		let destroyed_at = capture_backtrace();
+
+		// We hold the name lock while bumping the generation so that formatters always see a
+		// generation consistent with the names.
+		let mut info = self.slot.info.lock();
 
 		// Ensure that the lifetime is still alive
-		if slot_guard.gen != self.gen {
+		let new_gen = self.gen.get().saturating_add(1);
+
+		if self
+			.slot
+			.gen
+			.compare_exchange(self.gen.get(), new_gen, AcqRel, Acquire)
+			.is_err()
+		{
 			return false;
 		}
 
-		// See if we're disconnecting the lifetime from any of its dependencies.
-		if slot_guard.deps > 0 {
-			log::error!(
-				"Disconnected lifetime with name {:?} from {} dependenc{}.",
-				slot_guard.curr_name,
-				slot_guard.deps,
-				if slot_guard.deps > 0 { "ies" } else { "y" }
-			);
+		// Reset its state
+		let deps = self.slot.deps.swap(dep_tag(new_gen), AcqRel);
+		let deps = if deps & !DEP_COUNT_MASK == dep_tag(self.gen.get()) {
+			deps & DEP_COUNT_MASK
+		} else {
+			0
+		};
+
+		info.in_use = false;
+
+		let dead_name = info.curr_name.take();
+		stats::record_destroy(&dead_name);
+
+		let mut holders = mem::take(&mut info.holders).into_iter().collect::<Vec<_>>();
+		holders.sort_unstable_by_key(|&(id, _)| id);
+
+		// See if we're disconnecting the lifetime from any of its dependencies. We report this once
+		// the lock has been released since violation hooks may very well inspect this lifetime.
+		let violation = (deps > 0).then(|| DebugViolation::DisconnectedDependents {
+			lifetime: self,
+			name: label_name(&dead_name).into_owned(),
+			dependents: deps,
+			holders: holders
+				.iter()
+				.map(|(_, name)| label_name(name).into_owned())
+				.collect(),
+		});
+
+		// Move the generation into the slot's history
+		let history_depth = name_history_depth();
+		let created_at = info.curr_created_at.take();
+
+		while info.history.len() >= history_depth {
+			info.history.pop_front();
 		}
 
-		// Reset its state
-		slot_guard.gen = slot_guard.gen.saturating_add(1);
-		slot_guard.deps = 0;
-		slot_guard.dead_name = slot_guard.curr_name.take();
+		info.history.push_back(DeadGeneration {
+			gen: self.gen.get(),
+			name: dead_name,
+			created_at,
+			destroyed_at,
+		});
+
+		drop(info);
 
 		// Release the slot
-		if slot_guard.gen.get() != u64::MAX {
-			drop(slot_guard);
+		if new_gen != u64::MAX {
 			db::free_slot(self.slot);
 		} else {
 			log::error!(
@@ -187,50 +373,119 @@ impl Lifetime {
 			// (leak the slot)
 		}
 
+		if let Some(violation) = violation {
+			report_violation(violation);
+		}
+
 		true
 	}
 
 	pub fn destroy(self) {
 		if !self.try_destroy() {
-			log::error!(
-				"Attempted to destroy lifetime {:?}, which was already destroyed.",
-				self
-			);
+			report_violation(DebugViolation::double_destroy(
+				self,
+				format!(
+					"Attempted to destroy lifetime {:?}, which was already destroyed.",
+					self
+				),
+			));
+		}
+	}
+
+	pub fn add_dep_holder<L: DebugLabel>(self, name: L) -> Option<NonZeroU64> {
+		if !tracks_dependent_holders() {
+			return None;
+		}
+
+		let name = name.reify();
+		let mut info = self.slot.info.lock();
+
+		if self.is_condemned() {
+			return None;
 		}
+
+		let holder = NonZeroU64::new(info.next_holder).unwrap();
+		info.next_holder += 1;
+		info.holders.insert(holder, name);
+
+		Some(holder)
+	}
+
+	pub fn remove_dep_holder(self, holder: NonZeroU64) {
+		let mut info = self.slot.info.lock();
+
+		// Holder IDs are never reused by a given slot so we don't need to check the generation.
+		info.holders.remove(&holder);
+	}
+
+	pub fn dep_holder_name(self, holder: NonZeroU64) -> ReifiedDebugLabel {
+		let info = self.slot.info.lock();
+
+		info.holders.get(&holder).cloned().flatten()
 	}
 
 	pub fn debug_name(self) -> LifetimeName {
 		LifetimeName(self)
 	}
 
-	fn fmt_lifetime_name(self, slot_guard: &SlotDataInner) -> &str {
+	pub fn backtraces(self) -> Option<LifetimeBacktraces> {
+		let info = self.slot.info.lock();
+		let local_gen = self.gen.get();
+		let curr_gen = self.slot.gen.load(Acquire);
+
+		let backtraces = if local_gen == curr_gen {
+			LifetimeBacktraces {
+				created_at: info.curr_created_at.clone(),
+				destroyed_at: None,
+			}
+		} else if let Some(dead) = info.dead_generation(local_gen, curr_gen) {
+			LifetimeBacktraces {
+				created_at: dead.created_at.clone(),
+				destroyed_at: dead.destroyed_at.clone(),
+			}
+		} else {
+			return None;
+		};
+
+		(backtraces.created_at.is_some() || backtraces.destroyed_at.is_some()).then_some(backtraces)
+	}
+
+	fn fmt_lifetime_name(self, f: impl FnOnce(&str, bool) -> fmt::Result) -> fmt::Result {
+		let info = self.slot.info.lock();
 		let local_gen = self.gen.get();
-		let curr_gen = slot_guard.gen.get();
+		let curr_gen = self.slot.gen.load(Acquire);
 
 		let name = if local_gen == curr_gen {
-			Some(&slot_guard.curr_name)
-		} else if local_gen == curr_gen - 1 {
-			Some(&slot_guard.dead_name)
+			Some(&info.curr_name)
 		} else {
-			None
+			info.dead_generation(local_gen, curr_gen)
+				.map(|dead| &dead.name)
 		};
 
-		match name {
-			Some(Some(name)) => name,
-			Some(None) => "<name unspecified>",
-			None => "<name unavailable>",
-		}
+		let name = match name {
+			Some(name) => label_name(name),
+			None => Cow::Borrowed("<name unavailable>"),
+		};
+
+		f(&name, local_gen == curr_gen)
+	}
+}
+
+fn label_name(label: &ReifiedDebugLabel) -> Cow<'_, str> {
+	match label {
+		Some(label) => label.to_str(),
+		None => Cow::Borrowed(stats::UNSPECIFIED_LABEL),
 	}
 }
 
 impl fmt::Debug for Lifetime {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		let slot_guard = self.slot.0.lock();
-
-		f.debug_struct("Lifetime")
-			.field("name", &self.fmt_lifetime_name(&slot_guard))
-			.field("is_alive", &(slot_guard.gen == self.gen))
-			.finish_non_exhaustive()
+		self.fmt_lifetime_name(|name, is_alive| {
+			f.debug_struct("Lifetime")
+				.field("name", &name)
+				.field("is_alive", &is_alive)
+				.finish_non_exhaustive()
+		})
 	}
 }
 
@@ -239,15 +494,320 @@ pub struct LifetimeName(pub Lifetime);
 
 impl fmt::Debug for LifetimeName {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		let slot_guard = self.0.slot.0.lock();
-		fmt::Debug::fmt(self.0.fmt_lifetime_name(&slot_guard), f)
+		self.0.fmt_lifetime_name(|name, _| fmt::Debug::fmt(name, f))
 	}
 }
 
 impl fmt::Display for LifetimeName {
 	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-		let slot_guard = self.0.slot.0.lock();
-		f.write_str(self.0.fmt_lifetime_name(&slot_guard))
+		self.0.fmt_lifetime_name(|name, _| f.write_str(name))
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct LifetimeBacktraces {
+	pub created_at: Option<Arc<Backtrace>>,
+	pub destroyed_at: Option<Arc<Backtrace>>,
+}
+
+impl LifetimeBacktraces {
+	pub fn of(lifetime: impl DebugLifetimeWrapper) -> Option<Self> {
+		DebugLifetimeWrapper::as_debug_lifetime(lifetime)
+			.raw()
+			.and_then(Lifetime::backtraces)
+	}
+}
+
+impl fmt::Display for LifetimeBacktraces {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if let Some(created_at) = &self.created_at {
+			write!(f, "\n\nThe lifetime was created at:\n{created_at}")?;
+		}
+
+		if let Some(destroyed_at) = &self.destroyed_at {
+			write!(f, "\n\nThe lifetime was destroyed at:\n{destroyed_at}")?;
+		}
+
+		Ok(())
+	}
+}
+
+// === Stats === //
+
+#[derive(Debug, Clone, Default)]
+pub struct LifetimeStats {
+	pub allocated: u64,
+	pub alive: u64,
+	pub destroyed: u64,
+	pub dependents: u64,
+	pub by_label: Vec<LabelStats>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LabelStats {
+	pub label: String,
+	pub allocated: u64,
+	pub alive: u64,
+	pub destroyed: u64,
+	pub dependents: u64,
+}
+
+mod stats {
+	use std::{
+		borrow::Cow,
+		sync::{
+			atomic::{AtomicU64, Ordering::Relaxed},
+			Arc,
+		},
+	};
+
+	use fnv::FnvHashMap;
+	use parking_lot::Mutex;
+
+	use super::ReifiedDebugLabel;
+
+	// Labels generated with `format!` could otherwise make these maps grow without bounds.
+	const MAX_TRACKED_LABELS: usize = 1024;
+
+	pub(super) const UNSPECIFIED_LABEL: &str = "<name unspecified>";
+	pub(super) const OTHER_LABEL: &str = "<other labels>";
+
+	pub(super) static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+	pub(super) static DESTROYED: AtomicU64 = AtomicU64::new(0);
+
+	#[derive(Debug, Default)]
+	pub(super) struct LabelCounters {
+		pub allocated: u64,
+		pub destroyed: u64,
+	}
+
+	type LabelMap = FnvHashMap<Cow<'static, str>, LabelCounters>;
+	type LabelShard = Mutex<Option<LabelMap>>;
+
+	// Lifetimes are created and destroyed on every spawn and despawn so each thread counts into
+	// its own shard, which only ever gets contended while a report is being built.
+	static SHARDS: Mutex<Vec<Arc<LabelShard>>> = Mutex::new(Vec::new());
+
+	// Exited threads merge their counts into this shard so that thread churn doesn't leak shards.
+	// (also used by threads whose local shard has already been destroyed)
+	static FALLBACK_SHARD: LabelShard = Mutex::new(None);
+
+	pub(super) struct LocalShard(pub(super) Arc<LabelShard>);
+
+	impl Drop for LocalShard {
+		fn drop(&mut self) {
+			// We hold the shard list's lock while moving the counts over so that reports see them
+			// exactly once.
+			let mut shards = SHARDS.lock();
+			shards.retain(|shard| !Arc::ptr_eq(shard, &self.0));
+
+			let Some(by_label) = self.0.lock().take() else {
+				return;
+			};
+
+			let mut fallback = FALLBACK_SHARD.lock();
+			let fallback = fallback.get_or_insert_with(FnvHashMap::default);
+
+			for (label, counters) in by_label {
+				let merged = counters_for(fallback, Some(&label));
+				merged.allocated += counters.allocated;
+				merged.destroyed += counters.destroyed;
+			}
+		}
+	}
+
+	thread_local! {
+		pub(super) static LOCAL_SHARD: LocalShard = {
+			let shard = Arc::new(Mutex::new(None));
+			SHARDS.lock().push(shard.clone());
+			LocalShard(shard)
+		};
+	}
+
+	// Lazy labels are grouped by their template so that labels like `lazy_label!("zombie {}", i)`
+	// don't each get their own entry.
+	pub(super) fn label_key(label: &ReifiedDebugLabel) -> &str {
+		label
+			.as_ref()
+			.map_or(UNSPECIFIED_LABEL, |label| label.template())
+	}
+
+	fn counters_for<'a>(by_label: &'a mut LabelMap, label: Option<&str>) -> &'a mut LabelCounters {
+		let key = match label {
+			Some(label) if by_label.len() < MAX_TRACKED_LABELS || by_label.contains_key(label) => {
+				label
+			}
+			Some(_) => OTHER_LABEL,
+			None => UNSPECIFIED_LABEL,
+		};
+
+		if !by_label.contains_key(key) {
+			by_label.insert(Cow::Owned(key.to_string()), LabelCounters::default());
+		}
+
+		by_label.get_mut(key).unwrap()
+	}
+
+	fn with_counters(label: &ReifiedDebugLabel, f: fn(&mut LabelCounters)) {
+		let update_shard = |shard: &LabelShard| {
+			let mut by_label = shard.lock();
+			let by_label = by_label.get_or_insert_with(FnvHashMap::default);
+
+			f(counters_for(
+				by_label,
+				label.as_ref().map(|label| label.template()),
+			));
+		};
+
+		if LOCAL_SHARD
+			.try_with(|shard| update_shard(&shard.0))
+			.is_err()
+		{
+			update_shard(&FALLBACK_SHARD);
+		}
+	}
+
+	pub(super) fn record_alloc(label: &ReifiedDebugLabel) {
+		ALLOCATED.fetch_add(1, Relaxed);
+		with_counters(label, |counters| counters.allocated += 1);
+	}
+
+	pub(super) fn record_destroy(label: &ReifiedDebugLabel) {
+		DESTROYED.fetch_add(1, Relaxed);
+		with_counters(label, |counters| counters.destroyed += 1);
+	}
+
+	pub(super) fn merged_counters() -> FnvHashMap<String, LabelCounters> {
+		let shards = SHARDS.lock();
+		let mut merged = FnvHashMap::<String, LabelCounters>::default();
+
+		for shard in shards.iter().map(|shard| &**shard).chain([&FALLBACK_SHARD]) {
+			let Some(by_label) = &*shard.lock() else {
+				continue;
+			};
+
+			for (label, counters) in by_label {
+				let merged = merged.entry(label.to_string()).or_default();
+				merged.allocated += counters.allocated;
+				merged.destroyed += counters.destroyed;
+			}
+		}
+
+		merged
+	}
+}
+
+pub fn lifetime_stats() -> LifetimeStats {
+	let mut by_label = FnvHashMap::<String, LabelStats>::default();
+
+	for (label, counters) in stats::merged_counters() {
+		by_label.insert(
+			label.clone(),
+			LabelStats {
+				label,
+				allocated: counters.allocated,
+				destroyed: counters.destroyed,
+				..Default::default()
+			},
+		);
+	}
+
+	let mut alive = 0;
+	let mut dependents = 0;
+
+	for slot in db::all_slots() {
+		let info = slot.info.lock();
+		if !info.in_use {
+			continue;
+		}
+
+		let deps = slot.deps.load(Acquire) & DEP_COUNT_MASK;
+		alive += 1;
+		dependents += deps;
+
+		let label = stats::label_key(&info.curr_name);
+		let label = if by_label.contains_key(label) {
+			label
+		} else {
+			stats::OTHER_LABEL
+		};
+
+		let entry = by_label
+			.entry(label.to_string())
+			.or_insert_with(|| LabelStats {
+				label: label.to_string(),
+				..Default::default()
+			});
+
+		entry.alive += 1;
+		entry.dependents += deps;
+	}
+
+	let mut by_label = by_label.into_values().collect::<Vec<_>>();
+	by_label.sort_by(|a, b| b.alive.cmp(&a.alive).then_with(|| a.label.cmp(&b.label)));
+
+	LifetimeStats {
+		allocated: stats::ALLOCATED.load(Relaxed),
+		alive,
+		destroyed: stats::DESTROYED.load(Relaxed),
+		dependents,
+		by_label,
+	}
+}
+
+pub fn live_lifetimes() -> Vec<Lifetime> {
+	db::all_slots()
+		.filter_map(|slot| {
+			let info = slot.info.lock();
+			info.in_use.then(|| Lifetime {
+				slot,
+				gen: NonZeroU64::new(slot.gen.load(Acquire)).unwrap(),
+			})
+		})
+		.collect()
+}
+
+pub fn log_live_lifetimes() {
+	let stats = lifetime_stats();
+
+	if stats.alive == 0 {
+		return;
+	}
+
+	log::warn!(
+		"{} lifetime{} still alive:\n{}",
+		stats.alive,
+		if stats.alive == 1 { " is" } else { "s are" },
+		stats
+	);
+}
+
+impl fmt::Display for LifetimeStats {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		writeln!(
+			f,
+			"{} allocated, {} alive, {} destroyed, {} dependent{}",
+			self.allocated,
+			self.alive,
+			self.destroyed,
+			self.dependents,
+			if self.dependents == 1 { "" } else { "s" },
+		)?;
+
+		for label in &self.by_label {
+			writeln!(
+				f,
+				"    {:?}: {} allocated, {} alive, {} destroyed, {} dependent{}",
+				label.label,
+				label.allocated,
+				label.alive,
+				label.destroyed,
+				label.dependents,
+				if label.dependents == 1 { "" } else { "s" },
+			)?;
+		}
+
+		Ok(())
 	}
 }
 
@@ -291,6 +851,18 @@ mod debug_impl {
 			self.0.destroy();
 		}
 
+		pub fn add_dep_holder<L: DebugLabel>(self, name: L) -> Option<NonZeroU64> {
+			self.0.add_dep_holder(name)
+		}
+
+		pub fn remove_dep_holder(self, holder: NonZeroU64) {
+			self.0.remove_dep_holder(holder);
+		}
+
+		pub fn dep_holder_name(self, holder: NonZeroU64) -> ReifiedDebugLabel {
+			self.0.dep_holder_name(holder)
+		}
+
 		pub fn raw(self) -> Option<Lifetime> {
 			Some(self.0)
 		}
@@ -301,6 +873,27 @@ mod debug_impl {
 			Self::from_lifetime(lifetime)
 		}
 	}
+
+	#[derive(Debug, Copy, Clone)]
+	pub(super) struct DependentHolder(Option<NonZeroU64>);
+
+	impl DependentHolder {
+		pub(super) const NONE: Self = Self(None);
+
+		pub(super) fn add<L: DebugLabel>(lifetime: DebugLifetime, name: L) -> Self {
+			Self(lifetime.add_dep_holder(name))
+		}
+
+		pub(super) fn remove(self, lifetime: DebugLifetime) {
+			if let Some(holder) = self.0 {
+				lifetime.remove_dep_holder(holder);
+			}
+		}
+
+		pub(super) fn name(self, lifetime: DebugLifetime) -> ReifiedDebugLabel {
+			self.0.and_then(|holder| lifetime.dep_holder_name(holder))
+		}
+	}
 }
 
 #[allow(dead_code)]
@@ -341,6 +934,22 @@ mod release_impl {
 
 		pub fn destroy(self) {}
 
+		pub fn add_dep_holder<L: DebugLabel>(self, name: L) -> Option<NonZeroU64> {
+			let _ = name;
+
+			None
+		}
+
+		pub fn remove_dep_holder(self, holder: NonZeroU64) {
+			let _ = holder;
+		}
+
+		pub fn dep_holder_name(self, holder: NonZeroU64) -> ReifiedDebugLabel {
+			let _ = holder;
+
+			None
+		}
+
 		pub fn raw(self) -> Option<Lifetime> {
 			None
 		}
@@ -351,12 +960,44 @@ mod release_impl {
 			Self::from_lifetime(lifetime)
 		}
 	}
+
+	// Holders can't be registered on unchecked lifetimes so we don't waste any space on them.
+	#[derive(Debug, Copy, Clone)]
+	pub(super) struct DependentHolder;
+
+	impl DependentHolder {
+		pub(super) const NONE: Self = Self;
+
+		pub(super) fn add<L: DebugLabel>(lifetime: DebugLifetime, name: L) -> Self {
+			let _ = (lifetime, name);
+
+			Self
+		}
+
+		pub(super) fn remove(self, lifetime: DebugLifetime) {
+			let _ = lifetime;
+		}
+
+		pub(super) fn name(self, lifetime: DebugLifetime) -> ReifiedDebugLabel {
+			let _ = lifetime;
+
+			None
+		}
+	}
 }
 
-#[cfg(debug_assertions)]
+// `checked-lifetimes` wins if both features are enabled since cargo features are supposed to be
+// additive.
+#[cfg(any(
+	feature = "checked-lifetimes",
+	all(debug_assertions, not(feature = "unchecked-lifetimes")),
+))]
 pub use debug_impl::*;
 
-#[cfg(not(debug_assertions))]
+#[cfg(not(any(
+	feature = "checked-lifetimes",
+	all(debug_assertions, not(feature = "unchecked-lifetimes")),
+)))]
 pub use release_impl::*;
 
 impl Eq for DebugLifetime {}
@@ -490,35 +1131,91 @@ impl<L: DestructibleLifetime> Drop for OwnedLifetime<L> {
 	}
 }
 
-#[derive(Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
-pub struct Dependent<L: DebugLifetimeWrapper>(L);
+pub struct Dependent<L: DebugLifetimeWrapper> {
+	lifetime: L,
+	holder: DependentHolder,
+}
 
 impl<L: DebugLifetimeWrapper> Dependent<L> {
 	pub fn new(lifetime: L) -> Self {
 		L::as_debug_lifetime(lifetime).inc_dep();
-		Self(lifetime)
+		Self {
+			lifetime,
+			holder: DependentHolder::NONE,
+		}
+	}
+
+	pub fn new_named<N: DebugLabel>(lifetime: L, name: N) -> Self {
+		let debug_lifetime = L::as_debug_lifetime(lifetime);
+		debug_lifetime.inc_dep();
+
+		Self {
+			lifetime,
+			holder: DependentHolder::add(debug_lifetime, name),
+		}
 	}
 
 	pub fn get(&self) -> L {
-		self.0
+		self.lifetime
+	}
+
+	pub fn holder_name(&self) -> ReifiedDebugLabel {
+		self.holder.name(L::as_debug_lifetime(self.lifetime))
 	}
 
 	pub fn into_inner(self) -> L {
-		let lifetime = self.0;
+		let lifetime = self.lifetime;
 		drop(self);
 		lifetime
 	}
 }
 
+impl<L: DebugLifetimeWrapper + fmt::Debug> fmt::Debug for Dependent<L> {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_tuple("Dependent").field(&self.lifetime).finish()
+	}
+}
+
+// Holders are purely diagnostic so they don't participate in comparisons. This also keeps
+// `Borrow<L>` consistent with these impls.
+impl<L: DebugLifetimeWrapper + hash::Hash> hash::Hash for Dependent<L> {
+	fn hash<H: hash::Hasher>(&self, state: &mut H) {
+		self.lifetime.hash(state);
+	}
+}
+
+impl<L: DebugLifetimeWrapper + Eq> Eq for Dependent<L> {}
+
+impl<L: DebugLifetimeWrapper + PartialEq> PartialEq for Dependent<L> {
+	fn eq(&self, other: &Self) -> bool {
+		self.lifetime == other.lifetime
+	}
+}
+
+impl<L: DebugLifetimeWrapper + Ord> Ord for Dependent<L> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.lifetime.cmp(&other.lifetime)
+	}
+}
+
+impl<L: DebugLifetimeWrapper + PartialOrd> PartialOrd for Dependent<L> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		self.lifetime.partial_cmp(&other.lifetime)
+	}
+}
+
 impl<L: DebugLifetimeWrapper> Borrow<L> for Dependent<L> {
 	fn borrow(&self) -> &L {
-		&self.0
+		&self.lifetime
 	}
 }
 
 impl<L: DebugLifetimeWrapper> Clone for Dependent<L> {
 	fn clone(&self) -> Self {
-		Self::new(self.get())
+		match self.holder_name() {
+			Some(name) => Self::new_named(self.get(), name),
+			None => Self::new(self.get()),
+		}
 	}
 }
 
@@ -530,6 +1227,154 @@ impl<L: DebugLifetimeWrapper> From<L> for Dependent<L> {
 
 impl<L: DebugLifetimeWrapper> Drop for Dependent<L> {
 	fn drop(&mut self) {
-		L::as_debug_lifetime(self.0).dec_dep();
+		let lifetime = L::as_debug_lifetime(self.lifetime);
+		self.holder.remove(lifetime);
+		lifetime.dec_dep();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::thread;
+
+	use crate::debug::violation::ViolationKind;
+
+	use super::*;
+
+	#[test]
+	fn stats_merge_thread_counters() {
+		const LABEL: &str = "stats_merge_thread_counters";
+
+		let (alive, shard) = thread::spawn(|| {
+			Lifetime::new(LABEL).destroy();
+			let alive = Lifetime::new(LABEL);
+			let shard = stats::LOCAL_SHARD.with(|shard| Arc::downgrade(&shard.0));
+
+			(alive, shard)
+		})
+		.join()
+		.unwrap();
+
+		// Exited threads hand their counts over to the fallback shard.
+		assert!(shard.upgrade().is_none());
+
+		let stats = lifetime_stats();
+		let label = stats
+			.by_label
+			.iter()
+			.find(|label| label.label == LABEL)
+			.unwrap();
+		assert_eq!(label.allocated, 2);
+		assert_eq!(label.destroyed, 1);
+		assert_eq!(label.alive, 1);
+
+		alive.destroy();
+	}
+
+	#[test]
+	fn stale_handles_are_dead() {
+		let lifetime = Lifetime::new("stale");
+		assert!(lifetime.is_alive());
+
+		lifetime.destroy();
+		assert!(lifetime.is_condemned());
+		assert!(!lifetime.try_destroy());
+		assert!(!lifetime.try_inc_dep());
+
+		// Slots are recycled but stale handles must never observe their successors.
+		let successors = (0..64)
+			.map(|_| Lifetime::new("successor"))
+			.collect::<Vec<_>>();
+		assert!(lifetime.is_condemned());
+		assert!(successors.iter().all(|successor| successor.is_alive()));
+
+		for successor in successors {
+			successor.destroy();
+		}
+	}
+
+	#[test]
+	fn stale_dependents_are_ignored() {
+		let lifetime = Lifetime::new("stale dependents");
+		let (_, violations) = crate::testing::capture_violations(|| {
+			lifetime.destroy();
+
+			let successor = Lifetime::new("successor");
+			lifetime.dec_dep();
+			successor.destroy();
+		});
+
+		assert!(violations.is_empty(), "{violations:?}");
+	}
+
+	#[test]
+	fn concurrent_dependents() {
+		let lifetime = Lifetime::new("concurrent");
+
+		thread::scope(|s| {
+			for _ in 0..4 {
+				s.spawn(|| {
+					for _ in 0..1000 {
+						lifetime.inc_dep();
+						assert!(lifetime.is_alive());
+						lifetime.dec_dep();
+					}
+				});
+			}
+		});
+
+		crate::testing::assert_no_violations(|| lifetime.destroy());
+	}
+
+	#[test]
+	fn destroying_with_dependents_reports_holders() {
+		// `Dependent`s only track unchecked lifetimes through their `DebugLifetime`.
+		if !DebugLifetime::IS_ENABLED {
+			return;
+		}
+
+		set_track_dependent_holders(true);
+
+		let lifetime = Lifetime::new("held");
+		let named = Dependent::new_named(lifetime, "named");
+		let unnamed = Dependent::new(lifetime);
+
+		let (_, violation) =
+			crate::testing::expect_violation(ViolationKind::DisconnectedDependents, || {
+				lifetime.destroy()
+			});
+
+		let DebugViolation::DisconnectedDependents {
+			lifetime: dead,
+			name,
+			dependents,
+			holders,
+		} = &violation
+		else {
+			unreachable!();
+		};
+
+		assert_eq!(*dead, lifetime);
+		assert_eq!(name, "held");
+		assert_eq!(*dependents, 2);
+		assert_eq!(holders, &["named"]);
+		assert_eq!(
+			violation.to_string(),
+			"Disconnected lifetime with name \"held\" from 2 dependencies (held by \"named\", 1 unnamed)."
+		);
+
+		// Releasing dependents of a dead lifetime is fine.
+		crate::testing::assert_no_violations(|| drop((named, unnamed)));
+	}
+
+	#[test]
+	fn dependent_is_free_when_unchecked() {
+		let expected = if DebugLifetime::IS_ENABLED {
+			mem::size_of::<(DebugLifetime, Option<NonZeroU64>)>()
+		} else {
+			0
+		};
+
+		assert_eq!(mem::size_of::<Dependent<DebugLifetime>>(), expected);
 	}
 }
@@ -1,2 +1,4 @@
+pub mod borrow;
 pub mod label;
 pub mod lifetime;
+pub mod violation;
@@ -0,0 +1,278 @@
+use std::{cell::RefCell, error::Error, fmt, sync::Arc, time::Duration};
+
+use parking_lot::Mutex;
+
+use crate::entity::{ArchetypeId, Entity};
+
+use super::lifetime::{DebugLifetime, Lifetime, LifetimeBacktraces};
+
+// === DebugViolation === //
+
+#[derive(Debug, Clone)]
+pub enum DebugViolation {
+	UseAfterFree {
+		subject: ViolationSubject,
+		type_name: Option<&'static str>,
+		message: String,
+		backtraces: Option<LifetimeBacktraces>,
+	},
+	DoubleDestroy {
+		subject: ViolationSubject,
+		message: String,
+		backtraces: Option<LifetimeBacktraces>,
+	},
+	DisconnectedDependents {
+		lifetime: Lifetime,
+		name: String,
+		dependents: u64,
+		holders: Vec<String>,
+	},
+	ArchetypeMismatch {
+		entity: Entity,
+		expected: ArchetypeId,
+		message: String,
+	},
+	DuplicateComponent {
+		subject: ViolationSubject,
+		type_name: &'static str,
+		message: String,
+	},
+	MissingComponent {
+		subject: ViolationSubject,
+		type_name: &'static str,
+		message: String,
+	},
+	Leak {
+		type_name: &'static str,
+		count: usize,
+		message: String,
+	},
+	UnflushedUniverse {
+		count: usize,
+		pending_for: Option<Duration>,
+		message: String,
+	},
+	ExpiredEvents {
+		type_name: &'static str,
+		count: u64,
+		message: String,
+	},
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum ViolationKind {
+	UseAfterFree,
+	DoubleDestroy,
+	DisconnectedDependents,
+	ArchetypeMismatch,
+	DuplicateComponent,
+	MissingComponent,
+	Leak,
+	UnflushedUniverse,
+	ExpiredEvents,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum ViolationSubject {
+	None,
+	Lifetime(DebugLifetime),
+	Archetype(ArchetypeId),
+	Entity(Entity),
+}
+
+impl ViolationSubject {
+	pub fn lifetime(self) -> Option<DebugLifetime> {
+		match self {
+			Self::None => None,
+			Self::Lifetime(lifetime) => Some(lifetime),
+			Self::Archetype(archetype) => Some(archetype.lifetime),
+			Self::Entity(entity) => Some(entity.lifetime),
+		}
+	}
+}
+
+impl From<Lifetime> for ViolationSubject {
+	fn from(lifetime: Lifetime) -> Self {
+		Self::Lifetime(lifetime.into())
+	}
+}
+
+impl From<DebugLifetime> for ViolationSubject {
+	fn from(lifetime: DebugLifetime) -> Self {
+		Self::Lifetime(lifetime)
+	}
+}
+
+impl From<ArchetypeId> for ViolationSubject {
+	fn from(archetype: ArchetypeId) -> Self {
+		Self::Archetype(archetype)
+	}
+}
+
+impl From<Entity> for ViolationSubject {
+	fn from(entity: Entity) -> Self {
+		Self::Entity(entity)
+	}
+}
+
+impl DebugViolation {
+	pub fn use_after_free(subject: impl Into<ViolationSubject>, message: String) -> Self {
+		let subject = subject.into();
+
+		Self::UseAfterFree {
+			subject,
+			type_name: None,
+			message,
+			backtraces: subject.lifetime().and_then(LifetimeBacktraces::of),
+		}
+	}
+
+	pub fn double_destroy(subject: impl Into<ViolationSubject>, message: String) -> Self {
+		let subject = subject.into();
+
+		Self::DoubleDestroy {
+			subject,
+			message,
+			backtraces: subject.lifetime().and_then(LifetimeBacktraces::of),
+		}
+	}
+
+	pub fn use_after_free_with_type(
+		subject: impl Into<ViolationSubject>,
+		type_name: &'static str,
+		message: String,
+	) -> Self {
+		let subject = subject.into();
+
+		Self::UseAfterFree {
+			subject,
+			type_name: Some(type_name),
+			message,
+			backtraces: subject.lifetime().and_then(LifetimeBacktraces::of),
+		}
+	}
+
+	pub fn kind(&self) -> ViolationKind {
+		match self {
+			Self::UseAfterFree { .. } => ViolationKind::UseAfterFree,
+			Self::DoubleDestroy { .. } => ViolationKind::DoubleDestroy,
+			Self::DisconnectedDependents { .. } => ViolationKind::DisconnectedDependents,
+			Self::ArchetypeMismatch { .. } => ViolationKind::ArchetypeMismatch,
+			Self::DuplicateComponent { .. } => ViolationKind::DuplicateComponent,
+			Self::MissingComponent { .. } => ViolationKind::MissingComponent,
+			Self::Leak { .. } => ViolationKind::Leak,
+			Self::UnflushedUniverse { .. } => ViolationKind::UnflushedUniverse,
+			Self::ExpiredEvents { .. } => ViolationKind::ExpiredEvents,
+		}
+	}
+}
+
+impl fmt::Display for DebugViolation {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::UseAfterFree {
+				message,
+				backtraces,
+				..
+			}
+			| Self::DoubleDestroy {
+				message,
+				backtraces,
+				..
+			} => {
+				f.write_str(message)?;
+
+				if let Some(backtraces) = backtraces {
+					write!(f, "{backtraces}")?;
+				}
+
+				Ok(())
+			}
+			Self::ArchetypeMismatch { message, .. }
+			| Self::DuplicateComponent { message, .. }
+			| Self::MissingComponent { message, .. }
+			| Self::Leak { message, .. }
+			| Self::UnflushedUniverse { message, .. }
+			| Self::ExpiredEvents { message, .. } => f.write_str(message),
+			Self::DisconnectedDependents {
+				name,
+				dependents,
+				holders,
+				..
+			} => {
+				let unnamed = (*dependents as usize).saturating_sub(holders.len());
+				let holders = holders
+					.iter()
+					.map(|holder| format!("{holder:?}"))
+					.chain((unnamed > 0).then(|| format!("{unnamed} unnamed")))
+					.collect::<Vec<_>>()
+					.join(", ");
+
+				write!(
+					f,
+					"Disconnected lifetime with name {name:?} from {dependents} dependenc{} (held by {holders}).",
+					if *dependents == 1 { "y" } else { "ies" },
+				)
+			}
+		}
+	}
+}
+
+impl Error for DebugViolation {}
+
+// === ViolationPolicy === //
+
+#[derive(Clone, Default)]
+pub enum ViolationPolicy {
+	#[default]
+	Log,
+	Panic,
+	Hook(Arc<dyn Fn(&DebugViolation) + Send + Sync>),
+}
+
+impl fmt::Debug for ViolationPolicy {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Log => f.write_str("Log"),
+			Self::Panic => f.write_str("Panic"),
+			Self::Hook(_) => f.write_str("Hook(..)"),
+		}
+	}
+}
+
+impl ViolationPolicy {
+	pub fn hook(f: impl 'static + Fn(&DebugViolation) + Send + Sync) -> Self {
+		Self::Hook(Arc::new(f))
+	}
+}
+
+static GLOBAL_POLICY: Mutex<Option<ViolationPolicy>> = Mutex::new(None);
+
+thread_local! {
+	static THREAD_POLICY: RefCell<Option<ViolationPolicy>> = const { RefCell::new(None) };
+}
+
+pub fn set_violation_policy(policy: ViolationPolicy) -> ViolationPolicy {
+	GLOBAL_POLICY.lock().replace(policy).unwrap_or_default()
+}
+
+pub fn set_thread_violation_policy(policy: Option<ViolationPolicy>) -> Option<ViolationPolicy> {
+	THREAD_POLICY.with(|thread_policy| thread_policy.replace(policy))
+}
+
+pub fn violation_policy() -> ViolationPolicy {
+	THREAD_POLICY
+		.with(|thread_policy| thread_policy.borrow().clone())
+		.unwrap_or_else(|| GLOBAL_POLICY.lock().clone().unwrap_or_default())
+}
+
+#[track_caller]
+pub fn report_violation(violation: DebugViolation) {
+	match violation_policy() {
+		ViolationPolicy::Log => log::error!("{violation}"),
+		// Panicking while unwinding would abort the process so we log instead.
+		ViolationPolicy::Panic if std::thread::panicking() => log::error!("{violation}"),
+		ViolationPolicy::Panic => panic!("{violation}"),
+		ViolationPolicy::Hook(hook) => hook(&violation),
+	}
+}
@@ -14,6 +14,7 @@ use crate::{
 	debug::{
 		label::{DebugLabel, NO_LABEL},
 		lifetime::{DebugLifetime, DebugLifetimeWrapper, Lifetime, LifetimeWrapper, OwnedLifetime},
+		violation::{report_violation, DebugViolation},
 	},
 	universe::BuildableArchetype,
 	util::{free_list::FreeList, no_hash::RandIdGen},
@@ -30,6 +31,7 @@ pub struct ArchetypeId {
 }
 
 impl ArchetypeId {
+	#[track_caller]
 	pub fn get_in_universe(self, universe: &Universe) -> MutexGuard<Archetype> {
 		universe.archetype_by_id(self)
 	}
@@ -100,6 +102,7 @@ impl Entity {
 		&mut storage[self]
 	}
 
+	#[track_caller]
 	pub fn comp_in_universe<T: 'static + Send + Sync>(
 		self,
 		universe: &Universe,
@@ -107,6 +110,7 @@ impl Entity {
 		universe.comp(self)
 	}
 
+	#[track_caller]
 	pub fn comp_mut_in_universe<T: 'static + Send + Sync>(
 		self,
 		universe: &Universe,
@@ -114,6 +118,7 @@ impl Entity {
 		universe.comp_mut(self)
 	}
 
+	#[track_caller]
 	pub fn bypass_comp_in_universe<'r, T: 'static + Send + Sync + BypassExclusivity>(
 		self,
 		universe: &ExclusiveUniverse<'r>,
@@ -121,6 +126,7 @@ impl Entity {
 		universe.bypass_comp(self)
 	}
 
+	#[track_caller]
 	pub fn bypass_comp_mut_in_universe<'r, T: 'static + Send + Sync + BypassExclusivity>(
 		self,
 		universe: &ExclusiveUniverse<'r>,
@@ -165,6 +171,7 @@ impl<T> SingleEntity<T> {
 		&mut storage[self.as_entity()]
 	}
 
+	#[track_caller]
 	pub fn get_in_universe(self, universe: &Universe) -> MappedRwLockReadGuard<T>
 	where
 		T: 'static + Send + Sync,
@@ -172,6 +179,7 @@ impl<T> SingleEntity<T> {
 		universe.comp(self.as_entity())
 	}
 
+	#[track_caller]
 	pub fn get_mut_in_universe(self, universe: &Universe) -> MappedRwLockWriteGuard<T>
 	where
 		T: 'static + Send + Sync,
@@ -179,6 +187,7 @@ impl<T> SingleEntity<T> {
 		universe.comp_mut(self.as_entity())
 	}
 
+	#[track_caller]
 	pub fn bypass_get_in_universe<'r>(
 		self,
 		universe: &ExclusiveUniverse<'r>,
@@ -189,6 +198,7 @@ impl<T> SingleEntity<T> {
 		universe.bypass_comp(self.as_entity())
 	}
 
+	#[track_caller]
 	pub fn bypass_get_mut_in_universe<'r>(
 		self,
 		universe: &ExclusiveUniverse<'r>,
@@ -282,20 +292,25 @@ impl<M: ?Sized> Archetype<M> {
 
 	pub fn despawn(&mut self, entity: Entity) {
 		if cfg!(debug_assertions) && entity.archetype.id != self.id {
-			log::error!(
-				"Attempted to despawn {:?} from the non-owning archetype {:?}.",
+			report_violation(DebugViolation::ArchetypeMismatch {
 				entity,
-				self
-			);
+				expected: self.id(),
+				message: format!(
+					"Attempted to despawn {:?} from the non-owning archetype {:?}.",
+					entity, self
+				),
+			});
 			return;
 		}
 
 		if entity.lifetime.is_condemned() {
-			log::error!(
-				"Attempted to despawn the dead entity {:?} from the archetype {:?}",
+			report_violation(DebugViolation::double_destroy(
 				entity,
-				self
-			);
+				format!(
+					"Attempted to despawn the dead entity {:?} from the archetype {:?}",
+					entity, self
+				),
+			));
 			return;
 		}
 
@@ -342,6 +357,14 @@ impl<M: ?Sized> Archetype<M> {
 		self.lifetime.get()
 	}
 
+	pub fn len(&self) -> usize {
+		self.slots.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.slots.is_empty()
+	}
+
 	pub fn cast_marker<N: ?Sized>(self) -> Archetype<N> {
 		unsafe {
 			// Safety: This struct is `repr(C)` and `N` is only ever used in a `PhantomData`.
@@ -405,12 +428,16 @@ impl<T> WeakArchetypeMap<T> {
 		let old = self.insert(id, value);
 
 		if cfg!(debug_assertions) && old.is_some() {
-			log::warn!(
-				"`.add`'ed a component of type {} to an archetype {:?} that already had the component. \
-			     Use `.insert` instead if you wish to replace pre-existing components silently.",
-				type_name::<T>(),
-				id
-			);
+			report_violation(DebugViolation::DuplicateComponent {
+				subject: id.as_regular().into(),
+				type_name: type_name::<T>(),
+				message: format!(
+					"`.add`'ed a component of type {} to an archetype {:?} that already had the component. \
+					 Use `.insert` instead if you wish to replace pre-existing components silently.",
+					type_name::<T>(),
+					id
+				),
+			});
 			// (fallthrough)
 		}
 
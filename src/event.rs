@@ -1,18 +1,30 @@
 use std::{
 	any::type_name,
-	collections::HashMap,
-	mem,
-	num::NonZeroU32,
+	cmp::{Ordering, Reverse},
+	collections::{hash_map, BinaryHeap, HashMap, VecDeque},
+	mem::{self, Discriminant},
+	num::{NonZeroU32, NonZeroUsize},
 	ops::{Deref, DerefMut},
-	vec,
+	slice,
+	sync::{
+		atomic::{AtomicU64, AtomicUsize, Ordering::Relaxed},
+		Arc, Weak,
+	},
+	thread, vec,
 };
 
 use derive_where::derive_where;
+use fnv::FnvBuildHasher;
+use parking_lot::Mutex;
 
 use crate::{
-	debug::lifetime::{DebugLifetime, Dependent},
-	entity::hashers::ArchetypeBuildHasher,
-	ArchetypeId, Entity,
+	debug::{
+		lifetime::{DebugLifetime, DebugLifetimeWrapper, Dependent, Lifetime},
+		violation::{report_violation, DebugViolation},
+	},
+	entity::hashers::{ArchetypeBuildHasher, EntityBuildHasher},
+	util::free_list::FreeList,
+	ArchetypeId, ArchetypeMap, Entity, ExclusiveUniverse,
 };
 
 // === Aliases === //
@@ -28,28 +40,83 @@ pub type DestroyQueue = EventQueue<EntityDestroyEvent>;
 #[derive_where(Default)]
 pub struct EventQueue<E> {
 	runs: HashMap<NonZeroU32, (Dependent<DebugLifetime>, Vec<Event<E>>), ArchetypeBuildHasher>,
+	coalescing: EventCoalescing<E>,
+	coalesced: HashMap<(NonZeroU32, u32, Option<Lifetime>, Discriminant<E>), usize, FnvBuildHasher>,
+	next_seq: u64,
 	maybe_recursively_dispatched: bool,
 }
 
+#[derive_where(Debug, Copy, Clone, Default)]
+pub enum EventCoalescing<E> {
+	#[derive_where(default)]
+	KeepAll,
+	KeepFirst,
+	KeepLast,
+	Merge(fn(&mut E, E)),
+}
+
 impl<E> EventQueue<E> {
 	pub fn new() -> Self {
 		Self::default()
 	}
 
+	pub fn with_coalescing(coalescing: EventCoalescing<E>) -> Self {
+		let mut queue = Self::new();
+		queue.set_coalescing(coalescing);
+		queue
+	}
+
+	pub fn set_coalescing(&mut self, coalescing: EventCoalescing<E>) {
+		self.coalescing = coalescing;
+	}
+
 	pub fn push(&mut self, target: Entity, event: E) {
 		let run = self.runs.entry(target.archetype.id).or_insert_with(|| {
 			self.maybe_recursively_dispatched = true;
-			(Dependent::new(target.archetype.lifetime), Vec::new())
+			(
+				Dependent::new_named(target.archetype.lifetime, type_name::<Self>()),
+				Vec::new(),
+			)
 		});
 
+		// Events are coalesced by their target and their enum variant. `DebugLifetime`s compare
+		// equal regardless of their generation so we key on the raw lifetime to avoid merging the
+		// events of an entity into those of a dead entity which previously occupied its slot.
+		if !matches!(self.coalescing, EventCoalescing::KeepAll) {
+			let key = (
+				target.archetype.id,
+				target.slot,
+				target.lifetime.raw(),
+				mem::discriminant(&event),
+			);
+
+			if let Some(&index) = self.coalesced.get(&key) {
+				let existing = &mut run.1[index].event;
+
+				match self.coalescing {
+					EventCoalescing::KeepAll => unreachable!(),
+					EventCoalescing::KeepFirst => {}
+					EventCoalescing::KeepLast => *existing = event,
+					EventCoalescing::Merge(merge) => merge(existing, event),
+				}
+
+				return;
+			}
+
+			self.coalesced.insert(key, run.1.len());
+		}
+
 		run.1.push(Event {
 			slot: target.slot,
-			lifetime: Dependent::new(target.lifetime),
+			lifetime: Dependent::new_named(target.lifetime, type_name::<Self>()),
+			seq: self.next_seq,
 			event,
 		});
+		self.next_seq += 1;
 	}
 
 	pub fn flush_all(&mut self) -> impl Iterator<Item = EventQueueIter<E>> {
+		self.coalesced.clear();
 		mem::take(&mut self.runs)
 			.into_iter()
 			.map(|(arch_id, (arch_lt, events_list))| {
@@ -63,7 +130,27 @@ impl<E> EventQueue<E> {
 			})
 	}
 
+	pub fn flush_ordered(&mut self) -> impl Iterator<Item = (Entity, E)> {
+		let mut events = self
+			.flush_all()
+			.flat_map(|iter| {
+				let archetype = iter.arch();
+				iter.1.map(move |event| (archetype, event))
+			})
+			.collect::<Vec<_>>();
+
+		events.sort_unstable_by_key(|(_, event)| event.seq);
+		events
+			.into_iter()
+			.map(|(archetype, event)| event.into_tuple(archetype))
+	}
+
 	pub fn flush_in(&mut self, archetype: ArchetypeId) -> EventQueueIter<E> {
+		if !self.coalesced.is_empty() {
+			self.coalesced
+				.retain(|(arch_id, ..), _| *arch_id != archetype.id);
+		}
+
 		EventQueueIter(
 			archetype,
 			self.runs
@@ -73,6 +160,64 @@ impl<E> EventQueue<E> {
 		)
 	}
 
+	pub fn iter_all(&self) -> impl Iterator<Item = EventQueueIterRef<'_, E>> + '_ {
+		self.runs.iter().map(|(&arch_id, (arch_lt, events_list))| {
+			EventQueueIterRef(
+				ArchetypeId {
+					id: arch_id,
+					lifetime: arch_lt.get(),
+				},
+				events_list.iter(),
+			)
+		})
+	}
+
+	pub fn iter_in(&self, archetype: ArchetypeId) -> EventQueueIterRef<'_, E> {
+		EventQueueIterRef(
+			archetype,
+			self.runs
+				.get(&archetype.id)
+				.map_or(&[][..], |(_, events)| events.as_slice())
+				.iter(),
+		)
+	}
+
+	pub fn clear(&mut self) {
+		self.runs.clear();
+		self.coalesced.clear();
+	}
+
+	pub fn append(&mut self, other: &mut Self) {
+		other.coalesced.clear();
+
+		if !matches!(self.coalescing, EventCoalescing::KeepAll) {
+			for (target, event) in other.flush_ordered() {
+				self.push(target, event);
+			}
+			return;
+		}
+
+		// Events from `other` are ordered after our own events.
+		let seq_offset = self.next_seq;
+		self.next_seq += other.next_seq;
+
+		for (arch_id, (arch_lt, mut events_list)) in other.runs.drain() {
+			for event in &mut events_list {
+				event.seq += seq_offset;
+			}
+
+			match self.runs.entry(arch_id) {
+				hash_map::Entry::Occupied(entry) => {
+					entry.into_mut().1.append(&mut events_list);
+				}
+				hash_map::Entry::Vacant(entry) => {
+					self.maybe_recursively_dispatched = true;
+					entry.insert((arch_lt, events_list));
+				}
+			}
+		}
+	}
+
 	pub fn maybe_recursively_dispatched(&mut self) -> bool {
 		mem::replace(&mut self.maybe_recursively_dispatched, false)
 	}
@@ -91,11 +236,15 @@ impl<E> Drop for EventQueue<E> {
 		if !self.runs.is_empty() {
 			let leaked_count = self.runs.values().map(|(_, run)| run.len()).sum::<usize>();
 
-			log::error!(
-				"Leaked {leaked_count} event{} from {}",
-				if leaked_count == 1 { "" } else { "s" },
-				type_name::<Self>()
-			);
+			report_violation(DebugViolation::Leak {
+				type_name: type_name::<Self>(),
+				count: leaked_count,
+				message: format!(
+					"Leaked {leaked_count} event{} from {}",
+					if leaked_count == 1 { "" } else { "s" },
+					type_name::<Self>()
+				),
+			});
 		}
 	}
 }
@@ -104,19 +253,25 @@ impl<E> Drop for EventQueue<E> {
 struct Event<E> {
 	slot: u32,
 	lifetime: Dependent<DebugLifetime>,
+	seq: u64,
 	event: E,
 }
 
 impl<E> Event<E> {
+	fn entity(&self, archetype: ArchetypeId) -> Entity {
+		Entity {
+			slot: self.slot,
+			lifetime: self.lifetime.get(),
+			archetype,
+		}
+	}
+
 	fn into_tuple(self, archetype: ArchetypeId) -> (Entity, E) {
-		(
-			Entity {
-				slot: self.slot,
-				lifetime: self.lifetime.get(),
-				archetype,
-			},
-			self.event,
-		)
+		(self.entity(archetype), self.event)
+	}
+
+	fn as_tuple(&self, archetype: ArchetypeId) -> (Entity, &E) {
+		(self.entity(archetype), &self.event)
 	}
 }
 
@@ -127,6 +282,10 @@ impl<E> EventQueueIter<E> {
 	pub fn arch(&self) -> ArchetypeId {
 		self.0
 	}
+
+	pub fn iter(&self) -> EventQueueIterRef<'_, E> {
+		EventQueueIterRef(self.0, self.1.as_slice().iter())
+	}
 }
 
 impl<E> Iterator for EventQueueIter<E> {
@@ -153,13 +312,409 @@ impl<E> DoubleEndedIterator for EventQueueIter<E> {
 	}
 }
 
+#[derive(Debug)]
+#[derive_where(Clone)]
+pub struct EventQueueIterRef<'a, E>(ArchetypeId, slice::Iter<'a, Event<E>>);
+
+impl<'a, E> EventQueueIterRef<'a, E> {
+	pub fn arch(&self) -> ArchetypeId {
+		self.0
+	}
+}
+
+impl<'a, E> Iterator for EventQueueIterRef<'a, E> {
+	type Item = (Entity, &'a E);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.1.next().map(|e| e.as_tuple(self.0))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.1.size_hint()
+	}
+
+	fn count(self) -> usize {
+		self.1.count()
+	}
+}
+
+impl<E> ExactSizeIterator for EventQueueIterRef<'_, E> {}
+
+impl<E> DoubleEndedIterator for EventQueueIterRef<'_, E> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.1.next_back().map(|e| e.as_tuple(self.0))
+	}
+}
+
+// === ConcurrentEventQueue === //
+
+#[derive(Debug)]
+pub struct ConcurrentEventQueue<E> {
+	shards: Box<[Mutex<EventQueue<E>>]>,
+}
+
+impl<E> Default for ConcurrentEventQueue<E> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<E> ConcurrentEventQueue<E> {
+	pub fn new() -> Self {
+		Self::with_shards(thread::available_parallelism().map_or(1, NonZeroUsize::get))
+	}
+
+	pub fn with_shards(count: usize) -> Self {
+		assert_ne!(count, 0);
+
+		Self {
+			shards: (0..count).map(|_| Mutex::default()).collect(),
+		}
+	}
+
+	pub fn push(&self, target: Entity, event: E) {
+		static NEXT_SHARD: AtomicUsize = AtomicUsize::new(0);
+
+		thread_local! {
+			static SHARD_HINT: usize = NEXT_SHARD.fetch_add(1, Relaxed);
+		}
+
+		// Each thread is assigned a fixed shard so that threads pushing in parallel rarely contend
+		// with one another and events pushed by the same thread stay in order.
+		let shard = SHARD_HINT.with(|hint| *hint) % self.shards.len();
+		self.shards[shard].lock().push(target, event);
+	}
+
+	pub fn take(&mut self) -> EventQueue<E> {
+		let mut queue = EventQueue::new();
+
+		for shard in self.shards.iter_mut() {
+			queue.append(shard.get_mut());
+		}
+
+		queue
+	}
+
+	pub fn flush_all(&mut self) -> impl Iterator<Item = EventQueueIter<E>> {
+		self.take().flush_all()
+	}
+
+	pub fn flush_in(&mut self, archetype: ArchetypeId) -> EventQueueIter<E> {
+		let mut events = Vec::new();
+
+		for shard in self.shards.iter_mut() {
+			events.extend(shard.get_mut().flush_in(archetype).1);
+		}
+
+		EventQueueIter(archetype, events.into_iter())
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.shards.iter().all(|shard| shard.lock().is_empty())
+	}
+
+	pub fn has_remaining(&self) -> bool {
+		!self.is_empty()
+	}
+}
+
+// === EventScheduler === //
+
+// Events whose target has died by the time they come due are dropped. However, entities only
+// carry a generation when lifetimes are checked (see `DebugLifetime::IS_ENABLED`). Without those
+// checks, the scheduler can't tell a dead target apart from an entity which has since reused its
+// slot and delivers the event anyway. Code which relies on dead targets never receiving their
+// events in unchecked builds must call `cancel_entity` when despawning them.
+#[derive(Debug)]
+#[derive_where(Default)]
+pub struct EventScheduler<E> {
+	now: u64,
+	next_seq: u64,
+	pending: BinaryHeap<Reverse<ScheduledEvent<E>>>,
+}
+
+#[derive(Debug)]
+struct ScheduledEvent<E> {
+	due: u64,
+	seq: u64,
+	target: Entity,
+	event: E,
+}
+
+impl<E> Eq for ScheduledEvent<E> {}
+
+impl<E> PartialEq for ScheduledEvent<E> {
+	fn eq(&self, other: &Self) -> bool {
+		(self.due, self.seq) == (other.due, other.seq)
+	}
+}
+
+impl<E> Ord for ScheduledEvent<E> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		(self.due, self.seq).cmp(&(other.due, other.seq))
+	}
+}
+
+impl<E> PartialOrd for ScheduledEvent<E> {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl<E> EventScheduler<E> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn now(&self) -> u64 {
+		self.now
+	}
+
+	pub fn schedule_at(&mut self, tick: u64, target: Entity, event: E) {
+		let seq = self.next_seq;
+		self.next_seq += 1;
+
+		self.pending.push(Reverse(ScheduledEvent {
+			due: tick,
+			seq,
+			target,
+			event,
+		}));
+	}
+
+	pub fn schedule_in(&mut self, delay: u64, target: Entity, event: E) {
+		self.schedule_at(self.now.saturating_add(delay), target, event);
+	}
+
+	pub fn cancel_entity(&mut self, target: Entity) {
+		self.pending
+			.retain(|Reverse(scheduled)| scheduled.target != target);
+	}
+
+	pub fn advance(&mut self, ticks: u64, queue: &mut EventQueue<E>) {
+		self.advance_to(self.now.saturating_add(ticks), queue);
+	}
+
+	pub fn advance_to(&mut self, tick: u64, queue: &mut EventQueue<E>) {
+		self.now = self.now.max(tick);
+
+		while let Some(Reverse(scheduled)) = self.pending.peek() {
+			if scheduled.due > self.now {
+				break;
+			}
+
+			let Reverse(scheduled) = self.pending.pop().unwrap();
+
+			// Scheduled events don't hold a dependency on their target so that entities can be
+			// despawned while events are pending. Instead, we drop events whose target has died,
+			// which, like all lifetime checks, can only be detected when lifetimes are checked.
+			if scheduled.target.is_condemned() {
+				continue;
+			}
+
+			queue.push(scheduled.target, scheduled.event);
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.pending.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.pending.is_empty()
+	}
+}
+
+// === EventChannel === //
+
+#[derive(Debug)]
+#[derive_where(Default)]
+pub struct EventChannel<E> {
+	events: VecDeque<BroadcastEvent<E>>,
+	head: u64,
+	frame: u64,
+	max_age: Option<u64>,
+	readers: FreeList<AtomicU64>,
+	// (lets readers tell whether they're outliving their channel)
+	alive: Arc<()>,
+}
+
+// Like `EventScheduler`, we don't hold a dependency on the targets since events are retained
+// across frames and their targets may very well be despawned in the meantime. Readers are
+// expected to check that the targets are still alive.
+#[derive(Debug)]
+struct BroadcastEvent<E> {
+	target: Entity,
+	frame: u64,
+	event: E,
+}
+
+#[derive(Debug)]
+pub struct EventChannelReader {
+	id: u32,
+	cursor: u64,
+	channel: Weak<()>,
+}
+
+impl<E> EventChannel<E> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_max_age(frames: u64) -> Self {
+		Self {
+			max_age: Some(frames),
+			..Default::default()
+		}
+	}
+
+	pub fn add_reader(&mut self) -> EventChannelReader {
+		let cursor = self.tail();
+		let id = self.readers.alloc(AtomicU64::new(cursor));
+
+		EventChannelReader {
+			id,
+			cursor,
+			channel: Arc::downgrade(&self.alive),
+		}
+	}
+
+	pub fn remove_reader(&mut self, mut reader: EventChannelReader) {
+		self.readers.dealloc(reader.id);
+
+		// (an empty `Weak` tells the reader's destructor that it was unregistered)
+		reader.channel = Weak::new();
+	}
+
+	pub fn push(&mut self, target: Entity, event: E) {
+		self.events.push_back(BroadcastEvent {
+			target,
+			frame: self.frame,
+			event,
+		});
+	}
+
+	pub fn read<'a>(
+		&'a self,
+		reader: &mut EventChannelReader,
+	) -> impl Iterator<Item = (Entity, &'a E)> + 'a {
+		if reader.cursor < self.head {
+			let count = self.head - reader.cursor;
+
+			report_violation(DebugViolation::ExpiredEvents {
+				type_name: type_name::<Self>(),
+				count,
+				message: format!(
+					"{count} event{} expired from {} before they could be read.",
+					if count == 1 { "" } else { "s" },
+					type_name::<Self>(),
+				),
+			});
+			// (fallthrough)
+		}
+
+		let start = reader.cursor.max(self.head);
+		reader.cursor = self.tail();
+		self.readers[reader.id].store(reader.cursor, Relaxed);
+
+		self.events
+			.range((start - self.head) as usize..)
+			.map(|event| (event.target, &event.event))
+	}
+
+	pub fn unread_count(&self, reader: &EventChannelReader) -> usize {
+		(self.tail() - reader.cursor.max(self.head)) as usize
+	}
+
+	pub fn advance_frame(&mut self) {
+		self.frame += 1;
+		self.reclaim();
+	}
+
+	pub fn reclaim(&mut self) {
+		let min_cursor = self
+			.readers
+			.iter()
+			.map(|(_, cursor)| cursor.load(Relaxed))
+			.min()
+			.unwrap_or(self.tail());
+
+		while let Some(oldest) = self.events.front() {
+			let was_seen = self.head < min_cursor;
+			let has_expired = self
+				.max_age
+				.is_some_and(|max_age| self.frame - oldest.frame >= max_age);
+
+			if !was_seen && !has_expired {
+				break;
+			}
+
+			self.events.pop_front();
+			self.head += 1;
+		}
+	}
+
+	pub fn len(&self) -> usize {
+		self.events.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.events.is_empty()
+	}
+
+	fn tail(&self) -> u64 {
+		self.head + self.events.len() as u64
+	}
+}
+
+impl Drop for EventChannelReader {
+	fn drop(&mut self) {
+		// Readers of a dead channel have nothing left to unregister from.
+		if self.channel.strong_count() == 0 {
+			return;
+		}
+
+		report_violation(DebugViolation::Leak {
+			type_name: type_name::<Self>(),
+			count: 1,
+			message: String::from(
+				"Leaked an `EventChannelReader`. Use `EventChannel::remove_reader` to unregister it \
+				 lest it prevent its channel from reclaiming events.",
+			),
+		});
+	}
+}
+
 // === TaskQueue === //
 
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct TaskHandle(u64);
+
 #[derive(Debug)]
 #[derive_where(Default)]
 pub struct TaskQueue<T> {
-	task_stack: Vec<T>,
-	tasks_to_add: Vec<T>,
+	next_id: u64,
+	batch: u64,
+	tasks: HashMap<u64, QueuedTask<T>, FnvBuildHasher>,
+	ready: BinaryHeap<ReadyTask>,
+}
+
+#[derive(Debug)]
+struct QueuedTask<T> {
+	task: T,
+	priority: i32,
+	batch: u64,
+	blockers: usize,
+	dependents: Vec<u64>,
+}
+
+// Field order matters here: ready tasks are run by descending priority, then by most recent
+// batch, then in the order in which they were pushed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+struct ReadyTask {
+	priority: i32,
+	batch: u64,
+	id: Reverse<u64>,
 }
 
 impl<T> TaskQueue<T> {
@@ -167,42 +722,344 @@ impl<T> TaskQueue<T> {
 		Self::default()
 	}
 
-	pub fn push(&mut self, task: impl Into<T>) {
-		// These are queued in a separate buffer and moved into the main buffer during `next_task`
-		// to ensure that tasks are pushed in an intuitive order.
-		self.tasks_to_add.push(task.into());
+	pub fn push(&mut self, task: impl Into<T>) -> TaskHandle {
+		self.push_with(0, &[], task)
+	}
+
+	pub fn push_prioritized(&mut self, priority: i32, task: impl Into<T>) -> TaskHandle {
+		self.push_with(priority, &[], task)
+	}
+
+	pub fn push_after(&mut self, after: &[TaskHandle], task: impl Into<T>) -> TaskHandle {
+		self.push_with(0, after, task)
+	}
+
+	pub fn push_with(
+		&mut self,
+		priority: i32,
+		after: &[TaskHandle],
+		task: impl Into<T>,
+	) -> TaskHandle {
+		let id = self.next_id;
+		self.next_id += 1;
+
+		// Dependencies which have already been run or cancelled are trivially satisfied.
+		let mut blockers = 0;
+		for dep in after {
+			if let Some(dep) = self.tasks.get_mut(&dep.0) {
+				dep.dependents.push(id);
+				blockers += 1;
+			}
+		}
+
+		self.tasks.insert(
+			id,
+			QueuedTask {
+				task: task.into(),
+				priority,
+				batch: self.batch,
+				blockers,
+				dependents: Vec::new(),
+			},
+		);
+
+		if blockers == 0 {
+			self.ready.push(ReadyTask {
+				priority,
+				batch: self.batch,
+				id: Reverse(id),
+			});
+		}
+
+		TaskHandle(id)
+	}
+
+	pub fn cancel(&mut self, handle: TaskHandle) -> Option<T> {
+		// Stale entries in the `ready` heap are skipped by `next_task`.
+		let cancelled = self.tasks.remove(&handle.0)?;
+
+		// Dependencies only express ordering so tasks waiting on a cancelled task are still run.
+		self.release_dependents(cancelled.dependents);
+
+		Some(cancelled.task)
+	}
+
+	pub fn is_pending(&self, handle: TaskHandle) -> bool {
+		self.tasks.contains_key(&handle.0)
 	}
 
 	pub fn next_task(&mut self) -> Option<T> {
-		// Move all tasks from `tasks_to_add` to `task_stack`. This flips their order, which is
-		// desireable.
-		self.task_stack.reserve(self.tasks_to_add.len());
-		while let Some(to_add) = self.tasks_to_add.pop() {
-			self.task_stack.push(to_add);
+		// Tasks pushed after this point belong to a newer batch and will therefore run before
+		// every task of the same priority which is already in the queue. This gives us the
+		// intuitive depth-first ordering for nested tasks.
+		self.batch += 1;
+
+		while let Some(ready) = self.ready.pop() {
+			let Some(queued) = self.tasks.remove(&ready.id.0) else {
+				continue;
+			};
+
+			self.release_dependents(queued.dependents);
+
+			return Some(queued.task);
+		}
+
+		None
+	}
+
+	pub fn drain(&mut self, mut handler: impl FnMut(&mut Self, T)) {
+		while let Some(task) = self.next_task() {
+			handler(self, task);
 		}
+	}
 
-		// Now, pop off the next task to be ran.
-		self.task_stack.pop()
+	pub fn len(&self) -> usize {
+		self.tasks.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.tasks.is_empty()
 	}
 
 	pub fn clear_capacities(&mut self) {
-		self.task_stack = Vec::new();
-		self.tasks_to_add = Vec::new();
+		self.tasks.shrink_to_fit();
+		self.ready.shrink_to_fit();
+	}
+
+	fn release_dependents(&mut self, dependents: Vec<u64>) {
+		for id in dependents {
+			let Some(task) = self.tasks.get_mut(&id) else {
+				continue;
+			};
+
+			task.blockers -= 1;
+
+			if task.blockers == 0 {
+				self.ready.push(ReadyTask {
+					priority: task.priority,
+					batch: task.batch,
+					id: Reverse(id),
+				});
+			}
+		}
 	}
 }
 
 impl<T> Drop for TaskQueue<T> {
 	fn drop(&mut self) {
-		let remaining = self.task_stack.len() + self.tasks_to_add.len();
+		let remaining = self.tasks.len();
 
 		if remaining > 0 {
-			log::warn!(
-				"Leaked {} task{} on the `TaskQueue`.",
-				remaining,
-				if remaining == 1 { "" } else { "s" },
-			);
+			report_violation(DebugViolation::Leak {
+				type_name: type_name::<Self>(),
+				count: remaining,
+				message: format!(
+					"Leaked {} task{} on the `TaskQueue`.",
+					remaining,
+					if remaining == 1 { "" } else { "s" },
+				),
+			});
+		}
+	}
+}
+
+// === Signal === //
+
+func! {
+	pub fn SignalHandler<E>(cx: &mut ExclusiveUniverse, target: Entity, event: &E)
+}
+
+#[derive(Debug)]
+#[derive_where(Default)]
+pub struct Signal<E> {
+	next_id: u64,
+	global: Vec<(u64, SignalHandler<E>)>,
+	by_archetype: ArchetypeMap<Vec<(u64, SignalHandler<E>)>>,
+	by_entity: HashMap<
+		(Entity, Option<Lifetime>),
+		(Dependent<Entity>, Vec<(u64, SignalHandler<E>)>),
+		EntityBuildHasher,
+	>,
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct SignalConnection {
+	id: u64,
+	target: SignalTarget,
+}
+
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+enum SignalTarget {
+	Global,
+	Archetype(ArchetypeId),
+	Entity(Entity),
+}
+
+impl<E> Signal<E> {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn connect_global(&mut self, handler: impl Into<SignalHandler<E>>) -> SignalConnection {
+		let id = self.alloc_id();
+		self.global.push((id, handler.into()));
+
+		SignalConnection {
+			id,
+			target: SignalTarget::Global,
+		}
+	}
+
+	pub fn connect_archetype(
+		&mut self,
+		archetype: ArchetypeId,
+		handler: impl Into<SignalHandler<E>>,
+	) -> SignalConnection {
+		if archetype.is_condemned() {
+			report_violation(DebugViolation::use_after_free(
+				archetype,
+				format!("Connected a signal handler to the dead archetype {archetype:?}."),
+			));
+			// (fallthrough)
+		}
+
+		let id = self.alloc_id();
+		self.by_archetype
+			.entry(Dependent::new_named(archetype, type_name::<Self>()))
+			.or_default()
+			.push((id, handler.into()));
+
+		SignalConnection {
+			id,
+			target: SignalTarget::Archetype(archetype),
+		}
+	}
+
+	pub fn connect_entity(
+		&mut self,
+		target: Entity,
+		handler: impl Into<SignalHandler<E>>,
+	) -> SignalConnection {
+		if target.is_condemned() {
+			report_violation(DebugViolation::use_after_free(
+				target,
+				format!("Connected a signal handler to the dead entity {target:?}."),
+			));
+			// (fallthrough)
+		}
+
+		let id = self.alloc_id();
+		self.by_entity
+			.entry(Self::entity_key(target))
+			.or_insert_with(|| {
+				(
+					Dependent::new_named(target, type_name::<Self>()),
+					Vec::new(),
+				)
+			})
+			.1
+			.push((id, handler.into()));
+
+		SignalConnection {
+			id,
+			target: SignalTarget::Entity(target),
 		}
 	}
+
+	pub fn disconnect(&mut self, connection: SignalConnection) -> bool {
+		fn remove_handler<E>(handlers: &mut Vec<(u64, SignalHandler<E>)>, id: u64) -> bool {
+			let Some(index) = handlers.iter().position(|(other, _)| *other == id) else {
+				return false;
+			};
+
+			handlers.remove(index);
+			true
+		}
+
+		match connection.target {
+			SignalTarget::Global => remove_handler(&mut self.global, connection.id),
+			SignalTarget::Archetype(archetype) => {
+				let Some(handlers) = self.by_archetype.get_mut(&archetype) else {
+					return false;
+				};
+
+				let removed = remove_handler(handlers, connection.id);
+				if handlers.is_empty() {
+					self.by_archetype.remove(&archetype);
+				}
+				removed
+			}
+			SignalTarget::Entity(target) => {
+				let key = Self::entity_key(target);
+				let Some((_, handlers)) = self.by_entity.get_mut(&key) else {
+					return false;
+				};
+
+				let removed = remove_handler(handlers, connection.id);
+				if handlers.is_empty() {
+					self.by_entity.remove(&key);
+				}
+				removed
+			}
+		}
+	}
+
+	pub fn disconnect_archetype(&mut self, archetype: ArchetypeId) {
+		self.by_archetype.remove(&archetype);
+	}
+
+	pub fn disconnect_entity(&mut self, target: Entity) {
+		self.by_entity.remove(&Self::entity_key(target));
+	}
+
+	pub fn handlers_for(&self, target: Entity) -> Vec<SignalHandler<E>> {
+		let by_entity = self
+			.by_entity
+			.get(&Self::entity_key(target))
+			.into_iter()
+			.flat_map(|(_, handlers)| handlers);
+		let by_archetype = self
+			.by_archetype
+			.get(&target.archetype)
+			.into_iter()
+			.flatten();
+
+		by_entity
+			.chain(by_archetype)
+			.chain(&self.global)
+			.map(|(_, handler)| handler.clone())
+			.collect()
+	}
+
+	pub fn dispatch(cx: &mut ExclusiveUniverse, events: &mut EventQueue<E>)
+	where
+		E: 'static + Send + Sync,
+	{
+		for iter in events.flush_all() {
+			for (target, event) in iter {
+				// Handlers are collected before being invoked so that they are free to connect and
+				// disconnect handlers themselves.
+				let handlers = cx.resource_ref::<Self>().handlers_for(target);
+
+				for handler in handlers {
+					handler(cx, target, &event);
+				}
+			}
+		}
+	}
+
+	fn alloc_id(&mut self) -> u64 {
+		let id = self.next_id;
+		self.next_id += 1;
+		id
+	}
+
+	// Like `EventQueue`'s coalescing keys, entity handlers are keyed on the raw lifetime so that an
+	// entity reusing the slot of a dead entity doesn't inherit its handlers. Unchecked builds have
+	// no generation to key on so handlers must be disconnected before their target is despawned.
+	fn entity_key(target: Entity) -> (Entity, Option<Lifetime>) {
+		(target, target.lifetime.raw())
+	}
 }
 
 // === `func!` traits === //
@@ -223,7 +1080,7 @@ pub trait FuncMethodInjectorMut<T: ?Sized> {
 
 pub mod injectors {
 	use super::*;
-	use crate::{BypassExclusivity, ExclusiveUniverse};
+	use crate::{universe::BuildableResourceRw, BypassExclusivity, Universe};
 
 	// === ByExclusiveStorage === //
 
@@ -243,6 +1100,58 @@ pub mod injectors {
 
 		const INJECTOR: Self::Injector = |cx, me| cx.bypass_comp_mut(*me);
 	}
+
+	// === ByStorage === //
+
+	#[derive(Debug, Copy, Clone, Default)]
+	pub struct ByStorage;
+
+	impl<T: 'static + Send + Sync> FuncMethodInjectorRef<T> for ByStorage {
+		type Guard<'a> = parking_lot::MappedRwLockReadGuard<'a, T>;
+		type Injector = for<'i> fn(&mut &'i Universe, &mut Entity) -> Self::Guard<'i>;
+
+		const INJECTOR: Self::Injector = |cx, me| cx.comp(*me);
+	}
+
+	impl<T: 'static + Send + Sync> FuncMethodInjectorMut<T> for ByStorage {
+		type Guard<'a> = parking_lot::MappedRwLockWriteGuard<'a, T>;
+		type Injector = for<'i> fn(&mut &'i Universe, &mut Entity) -> Self::Guard<'i>;
+
+		const INJECTOR: Self::Injector = |cx, me| cx.comp_mut(*me);
+	}
+
+	// === ByResource === //
+
+	#[derive(Debug, Copy, Clone, Default)]
+	pub struct ByResource;
+
+	impl<T: BuildableResourceRw> FuncMethodInjectorRef<T> for ByResource {
+		type Guard<'a> = parking_lot::RwLockReadGuard<'a, T>;
+		type Injector = for<'i> fn(&mut &'i Universe) -> Self::Guard<'i>;
+
+		const INJECTOR: Self::Injector = |cx| cx.resource_ref();
+	}
+
+	impl<T: BuildableResourceRw> FuncMethodInjectorMut<T> for ByResource {
+		type Guard<'a> = parking_lot::RwLockWriteGuard<'a, T>;
+		type Injector = for<'i> fn(&mut &'i Universe) -> Self::Guard<'i>;
+
+		const INJECTOR: Self::Injector = |cx| cx.resource_mut();
+	}
+
+	// === ByArchetypeMeta === //
+
+	// Archetype metadata is immutable once registered so this injector only supports `&self`
+	// methods.
+	#[derive(Debug, Copy, Clone, Default)]
+	pub struct ByArchetypeMeta;
+
+	impl<T: 'static + Send + Sync> FuncMethodInjectorRef<T> for ByArchetypeMeta {
+		type Guard<'a> = &'a T;
+		type Injector = for<'i> fn(&mut &'i Universe, &mut Entity) -> Self::Guard<'i>;
+
+		const INJECTOR: Self::Injector = |cx, me| cx.archetype_meta(me.archetype);
+	}
 }
 
 // === `func!` macro === //
@@ -250,7 +1159,7 @@ pub mod injectors {
 #[doc(hidden)]
 pub mod macro_internal {
 	use super::{FuncMethodInjectorMut, FuncMethodInjectorRef};
-	use std::ops::DerefMut;
+	use std::{mem, ops::DerefMut, ptr::NonNull};
 
 	pub trait FuncMethodInjectorRefGetGuard<T: ?Sized> {
 		type GuardHelper<'a>: Deref<Target = T>;
@@ -276,6 +1185,60 @@ pub mod macro_internal {
 		type GuardHelper<'a> = G::Guard<'a>;
 	}
 
+	// We store static handlers as raw pointers rather than `&'static F` because the latter would
+	// require every generic parameter of the delegate to be `'static`.
+	pub enum FuncHandler<F: ?Sized> {
+		Static(NonNull<F>),
+		Shared(Arc<F>),
+	}
+
+	// Safety: `Static` handlers are shared references to `F` so this is the same logic as `&F`.
+	unsafe impl<F: ?Sized + Send + Sync> Send for FuncHandler<F> {}
+
+	unsafe impl<F: ?Sized + Send + Sync> Sync for FuncHandler<F> {}
+
+	pub const fn static_handler<F: ?Sized>(handler: &'static F) -> NonNull<F> {
+		unsafe {
+			// Safety: references are never null.
+			NonNull::new_unchecked(handler as *const F as *mut F)
+		}
+	}
+
+	// Zero-sized handlers (e.g. plain `fn` items and closures without captures) can be leaked for
+	// free since boxing them never allocates. We can't do this for handlers with drop glue, however,
+	// since they'd never get dropped.
+	pub const fn can_leak_handler<F>() -> bool {
+		mem::size_of::<F>() == 0 && !mem::needs_drop::<F>()
+	}
+
+	pub fn leak_handler<F: 'static>(handler: F) -> NonNull<F> {
+		debug_assert!(can_leak_handler::<F>());
+		static_handler(Box::leak(Box::new(handler)))
+	}
+
+	impl<F: ?Sized> Clone for FuncHandler<F> {
+		fn clone(&self) -> Self {
+			match self {
+				Self::Static(handler) => Self::Static(*handler),
+				Self::Shared(handler) => Self::Shared(handler.clone()),
+			}
+		}
+	}
+
+	impl<F: ?Sized> Deref for FuncHandler<F> {
+		type Target = F;
+
+		fn deref(&self) -> &Self::Target {
+			match self {
+				Self::Static(handler) => unsafe {
+					// Safety: `Static` handlers always originate from `'static` references.
+					handler.as_ref()
+				},
+				Self::Shared(handler) => handler,
+			}
+		}
+	}
+
 	pub use std::{
 		clone::Clone,
 		convert::From,
@@ -397,8 +1360,7 @@ macro_rules! func {
 			$($where_token)*
 		)? {
 			_ty: ($($($crate::event::macro_internal::PhantomData<$generic>,)*)?),
-			// TODO: Optimize the internal representation to avoid allocations for context-less handlers.
-			handler: $crate::event::macro_internal::Arc<
+			handler: $crate::event::macro_internal::FuncHandler<
 				dyn
 					$($(for<$($fn_lt),*>)?)?
 					Fn($($para),*) $(-> $ret)? +
@@ -422,7 +1384,33 @@ macro_rules! func {
 			{
 				Self {
 					_ty: ($($($crate::event::macro_internal::PhantomData::<$generic>,)*)?),
-					handler: $crate::event::macro_internal::Arc::new(handler),
+					handler: if $crate::event::macro_internal::can_leak_handler::<Func>() {
+						$crate::event::macro_internal::FuncHandler::Static(
+							$crate::event::macro_internal::leak_handler(handler),
+						)
+					} else {
+						$crate::event::macro_internal::FuncHandler::Shared(
+							$crate::event::macro_internal::Arc::new(handler),
+						)
+					},
+				}
+			}
+
+			#[allow(unused)]
+			pub const fn new_static(
+				handler: &'static (
+					dyn
+						$($(for<$($fn_lt),*>)?)?
+						Fn($($para),*) $(-> $ret)? +
+							$crate::event::macro_internal::Send +
+							$crate::event::macro_internal::Sync
+				),
+			) -> Self {
+				Self {
+					_ty: ($($($crate::event::macro_internal::PhantomData::<$generic>,)*)?),
+					handler: $crate::event::macro_internal::FuncHandler::Static(
+						$crate::event::macro_internal::static_handler(handler),
+					),
 				}
 			}
 		}
@@ -488,3 +1476,490 @@ macro_rules! func {
 }
 
 pub use func;
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		debug::{lifetime::set_track_dependent_holders, violation::ViolationKind},
+		universe::BuildableResourceRw,
+		Archetype, Universe,
+	};
+
+	use super::*;
+
+	#[test]
+	fn queue_iterates_without_draining() {
+		let mut arch_a = Archetype::<()>::new("a");
+		let mut arch_b = Archetype::<()>::new("b");
+		let a = arch_a.spawn("a");
+		let b = arch_b.spawn("b");
+
+		let mut queue = EventQueue::new();
+		queue.push(a, 1);
+		queue.push(b, 2);
+		queue.push(a, 3);
+
+		assert_eq!(
+			queue.iter_in(a.archetype).collect::<Vec<_>>(),
+			[(a, &1), (a, &3)]
+		);
+		assert_eq!(queue.iter_all().map(|iter| iter.count()).sum::<usize>(), 3);
+		assert!(queue.maybe_recursively_dispatched());
+		assert!(!queue.maybe_recursively_dispatched());
+
+		assert_eq!(queue.flush_in(b.archetype).collect::<Vec<_>>(), [(b, 2)]);
+		assert!(queue.has_remaining());
+
+		queue.clear();
+		assert!(queue.is_empty());
+
+		arch_a.despawn(a);
+		arch_b.despawn(b);
+	}
+
+	#[test]
+	fn queue_flushes_in_push_order() {
+		let mut arch_a = Archetype::<()>::new("a");
+		let mut arch_b = Archetype::<()>::new("b");
+		let a = arch_a.spawn("a");
+		let b = arch_b.spawn("b");
+
+		let mut queue = EventQueue::new();
+		queue.push(a, 1);
+		queue.push(b, 2);
+		queue.push(a, 3);
+
+		// Appended events are ordered after the ones already in the queue.
+		let mut other = EventQueue::new();
+		other.push(b, 4);
+		queue.append(&mut other);
+		assert!(other.is_empty());
+
+		assert_eq!(
+			queue.flush_ordered().collect::<Vec<_>>(),
+			[(a, 1), (b, 2), (a, 3), (b, 4)],
+		);
+		assert!(queue.is_empty());
+
+		arch_a.despawn(a);
+		arch_b.despawn(b);
+	}
+
+	#[test]
+	fn queue_reports_leaked_events() {
+		let mut arch = Archetype::<()>::new("archetype");
+		let target = arch.spawn("target");
+
+		let mut queue = EventQueue::new();
+		queue.push(target, 1);
+		queue.push(target, 2);
+
+		let (_, violation) = crate::testing::expect_violation(ViolationKind::Leak, || drop(queue));
+
+		let DebugViolation::Leak {
+			type_name, count, ..
+		} = violation
+		else {
+			unreachable!();
+		};
+
+		assert_eq!(type_name, std::any::type_name::<EventQueue<i32>>());
+		assert_eq!(count, 2);
+
+		arch.despawn(target);
+	}
+
+	#[test]
+	fn channel_targets_can_be_despawned() {
+		let mut arch = Archetype::<()>::new("archetype");
+		let target = arch.spawn("target");
+
+		let mut channel = EventChannel::new();
+		let mut reader = channel.add_reader();
+		channel.push(target, 1);
+
+		assert_eq!(
+			channel.read(&mut reader).collect::<Vec<_>>(),
+			[(target, &1)]
+		);
+
+		// The event is still retained for other readers.
+		crate::testing::assert_no_violations(|| arch.despawn(target));
+		assert_eq!(channel.len(), 1);
+
+		channel.remove_reader(reader);
+		channel.advance_frame();
+		assert!(channel.is_empty());
+	}
+
+	#[test]
+	fn channel_misuse_is_reported() {
+		let mut arch = Archetype::<()>::new("archetype");
+		let target = arch.spawn("target");
+
+		let mut channel = EventChannel::with_max_age(1);
+		let mut reader = channel.add_reader();
+		channel.push(target, 1);
+		channel.push(target, 2);
+		channel.advance_frame();
+
+		let (events, violation) =
+			crate::testing::expect_violation(ViolationKind::ExpiredEvents, || {
+				channel.read(&mut reader).count()
+			});
+		assert_eq!(events, 0);
+		assert!(matches!(
+			violation,
+			DebugViolation::ExpiredEvents { count: 2, .. }
+		));
+
+		crate::testing::expect_violation(ViolationKind::Leak, || drop(reader));
+
+		// Readers which outlive their channel can't be unregistered so they're dropped silently.
+		let reader = channel.add_reader();
+		drop(channel);
+		crate::testing::assert_no_violations(|| drop(reader));
+
+		arch.despawn(target);
+	}
+
+	#[test]
+	fn coalescing_distinguishes_reused_slots() {
+		if !DebugLifetime::IS_ENABLED {
+			return;
+		}
+
+		let mut arch = Archetype::<()>::new("archetype");
+		let mut queue = EventQueue::with_coalescing(EventCoalescing::KeepLast);
+
+		let dead = arch.spawn("dead");
+		queue.push(dead, 1);
+		queue.push(dead, 2);
+
+		// Despawning an entity with pending events is a violation in its own right.
+		crate::testing::expect_violation(ViolationKind::DisconnectedDependents, || {
+			arch.despawn(dead)
+		});
+
+		let alive = arch.spawn("alive");
+		assert_eq!(alive.slot, dead.slot);
+		queue.push(alive, 3);
+
+		let events = queue.flush_ordered().collect::<Vec<_>>();
+		assert_eq!(events.len(), 2);
+		assert_eq!(events[0].0.lifetime.raw(), dead.lifetime.raw());
+		assert_eq!(events[0].1, 2);
+		assert_eq!(events[1].0.lifetime.raw(), alive.lifetime.raw());
+		assert_eq!(events[1].1, 3);
+
+		arch.despawn(alive);
+	}
+
+	#[test]
+	fn tasks_run_depth_first() {
+		let mut queue = TaskQueue::<&str>::new();
+		queue.push("a");
+		queue.push("b");
+
+		let mut order = Vec::new();
+		queue.drain(|queue, task| {
+			order.push(task);
+
+			if task == "a" {
+				queue.push("a.1");
+				queue.push("a.2");
+			} else if task == "a.1" {
+				queue.push("a.1.1");
+			}
+		});
+
+		assert_eq!(order, ["a", "a.1", "a.1.1", "a.2", "b"]);
+	}
+
+	#[test]
+	fn tasks_run_by_priority() {
+		let mut queue = TaskQueue::<&str>::new();
+		queue.push("normal");
+		queue.push_prioritized(-1, "low");
+		queue.push_prioritized(1, "high");
+		queue.push_prioritized(1, "high 2");
+
+		let mut order = Vec::new();
+		queue.drain(|queue, task| {
+			order.push(task);
+
+			// Nested tasks only take precedence over tasks of the same priority.
+			if task == "high" {
+				queue.push("nested normal");
+				queue.push_prioritized(1, "nested high");
+			}
+		});
+
+		assert_eq!(
+			order,
+			[
+				"high",
+				"nested high",
+				"high 2",
+				"nested normal",
+				"normal",
+				"low"
+			]
+		);
+	}
+
+	#[test]
+	fn tasks_run_after_their_dependencies() {
+		let mut queue = TaskQueue::<&str>::new();
+		let a = queue.push("a");
+		let b = queue.push_prioritized(-1, "b");
+		let c = queue.push_after(&[a, b], "c");
+		queue.push_with(1, &[c], "d");
+
+		// Handles to tasks which have already run are ignored.
+		assert_eq!(queue.next_task(), Some("a"));
+		assert!(!queue.is_pending(a));
+		queue.push_after(&[a], "e");
+
+		let mut order = Vec::new();
+		queue.drain(|_, task| order.push(task));
+		assert_eq!(order, ["e", "b", "c", "d"]);
+	}
+
+	#[test]
+	fn cancelling_tasks_releases_dependents() {
+		let mut queue = TaskQueue::<&str>::new();
+		let a = queue.push_prioritized(-1, "a");
+		let b = queue.push_after(&[a], "b");
+		let c = queue.push("c");
+
+		assert_eq!(queue.cancel(a), Some("a"));
+		assert_eq!(queue.cancel(a), None);
+		assert!(queue.is_pending(b));
+		assert_eq!(queue.cancel(c), Some("c"));
+
+		assert_eq!(queue.next_task(), Some("b"));
+		assert_eq!(queue.next_task(), None);
+		assert!(queue.is_empty());
+	}
+
+	#[test]
+	fn duplicate_dependencies_are_counted_once() {
+		let mut queue = TaskQueue::<&str>::new();
+		let a = queue.push_prioritized(-1, "a");
+		let b = queue.push_prioritized(-1, "b");
+		let c = queue.push_after(&[a, a, b], "c");
+
+		assert_eq!(queue.next_task(), Some("a"));
+		assert!(queue.is_pending(c));
+		assert_eq!(queue.next_task(), Some("b"));
+		assert_eq!(queue.next_task(), Some("c"));
+
+		// The same goes for cancelled dependencies.
+		let d = queue.push_prioritized(-1, "d");
+		queue.push_after(&[d, d], "e");
+		queue.cancel(d);
+		assert_eq!(queue.next_task(), Some("e"));
+		assert!(queue.is_empty());
+	}
+
+	func! {
+		fn StaticFunc(value: u32) -> u32
+	}
+
+	func! {
+		fn EntityMethod(&'a self [cx: &'a Universe, me: Entity], amount: u32) -> u32
+	}
+
+	func! {
+		fn UniverseMethod(&'a self [cx: &'a Universe], amount: u32) -> u32
+	}
+
+	#[derive(Debug)]
+	struct Counter(u32);
+
+	impl BuildableResourceRw for Counter {
+		fn create(_universe: &Universe) -> Self {
+			Self(10)
+		}
+	}
+
+	#[test]
+	fn static_funcs_do_not_allocate() {
+		fn double(value: u32) -> u32 {
+			value * 2
+		}
+
+		fn ignore_event(_cx: &mut ExclusiveUniverse, _target: Entity, _event: &i32) {}
+
+		static DOUBLE: StaticFunc = StaticFunc::new_static(&double);
+		static IGNORE_EVENT: SignalHandler<i32> = SignalHandler::new_static(&ignore_event);
+
+		assert_eq!(DOUBLE(3), 6);
+		assert!(matches!(
+			DOUBLE.handler,
+			macro_internal::FuncHandler::Static(_)
+		));
+		assert!(matches!(
+			IGNORE_EVENT.clone().handler,
+			macro_internal::FuncHandler::Static(_)
+		));
+
+		// Zero-sized handlers are leaked rather than reference-counted.
+		let increment = StaticFunc::new(|value| value + 1);
+		assert_eq!(increment(3), 4);
+		assert!(matches!(
+			increment.handler,
+			macro_internal::FuncHandler::Static(_)
+		));
+
+		let offset = 2;
+		let add_offset = StaticFunc::new(move |value| value + offset);
+		assert_eq!(add_offset.clone()(3), 5);
+		assert!(matches!(
+			add_offset.handler,
+			macro_internal::FuncHandler::Shared(_)
+		));
+	}
+
+	#[test]
+	fn method_injectors() {
+		let universe = Universe::new();
+		let arch = universe.create_archetype::<()>("archetype");
+		let target = universe.archetype_by_id(arch.id()).spawn("target");
+
+		universe.storage_mut::<Counter>().add(target, Counter(1));
+		universe.annotate_archetype(arch.id(), Counter(100));
+
+		// ByStorage
+		let get = EntityMethod::new_method_ref(
+			injectors::ByStorage,
+			|counter: &Counter, _, _, amount| counter.0 + amount,
+		);
+		let add = EntityMethod::new_method_mut(
+			injectors::ByStorage,
+			|counter: &mut Counter, _, _, amount| {
+				counter.0 += amount;
+				counter.0
+			},
+		);
+		assert_eq!(add(&universe, target, 2), 3);
+		assert_eq!(get(&universe, target, 1), 4);
+
+		// ByResource
+		let get = UniverseMethod::new_method_ref(
+			injectors::ByResource,
+			|counter: &Counter, _, amount| counter.0 + amount,
+		);
+		let add = UniverseMethod::new_method_mut(
+			injectors::ByResource,
+			|counter: &mut Counter, _, amount| {
+				counter.0 += amount;
+				counter.0
+			},
+		);
+		assert_eq!(add(&universe, 5), 15);
+		assert_eq!(get(&universe, 1), 16);
+
+		// ByArchetypeMeta
+		let get = EntityMethod::new_method_ref(
+			injectors::ByArchetypeMeta,
+			|meta: &Counter, _, _, amount| meta.0 + amount,
+		);
+		assert_eq!(get(&universe, target, 1), 101);
+
+		universe.storage_mut::<Counter>().remove(target);
+		universe.archetype_by_id(arch.id()).despawn(target);
+	}
+
+	#[test]
+	fn signals_distinguish_reused_slots() {
+		if !DebugLifetime::IS_ENABLED {
+			return;
+		}
+
+		let mut universe = Universe::new();
+		let mut arch = Archetype::<()>::new("archetype");
+		let received = Arc::new(Mutex::new(Vec::new()));
+
+		let dead = arch.spawn("dead");
+		universe
+			.resource_mut::<Signal<i32>>()
+			.connect_entity(dead, {
+				let received = received.clone();
+				SignalHandler::new(move |_, target, event| received.lock().push((target, *event)))
+			});
+
+		let mut queue = EventQueue::new();
+		queue.push(dead, 1);
+		Signal::dispatch(&mut universe.as_exclusive(), &mut queue);
+		assert_eq!(mem::take(&mut *received.lock()), [(dead, 1)]);
+
+		// Despawning an entity with connected handlers is a violation in its own right.
+		crate::testing::expect_violation(ViolationKind::DisconnectedDependents, || {
+			arch.despawn(dead)
+		});
+
+		let alive = arch.spawn("alive");
+		assert_eq!(alive.slot, dead.slot);
+
+		queue.push(alive, 2);
+		Signal::dispatch(&mut universe.as_exclusive(), &mut queue);
+		assert!(received.lock().is_empty());
+
+		universe
+			.resource_mut::<Signal<i32>>()
+			.disconnect_entity(dead);
+		arch.despawn(alive);
+	}
+
+	#[test]
+	fn scheduler_drops_dead_targets() {
+		let mut arch = Archetype::<()>::new("archetype");
+		let dead = arch.spawn("dead");
+		let alive = arch.spawn("alive");
+
+		let mut scheduler = EventScheduler::new();
+		let mut queue = EventQueue::new();
+		scheduler.schedule_in(2, dead, 1);
+		scheduler.schedule_in(1, alive, 2);
+		arch.despawn(dead);
+
+		scheduler.advance(1, &mut queue);
+		assert_eq!(queue.flush_ordered().collect::<Vec<_>>(), [(alive, 2)]);
+
+		scheduler.advance(1, &mut queue);
+		assert!(scheduler.is_empty());
+		assert_eq!(queue.is_empty(), DebugLifetime::IS_ENABLED);
+
+		queue.clear();
+		arch.despawn(alive);
+	}
+
+	#[test]
+	fn disconnection_names_queue() {
+		if !DebugLifetime::IS_ENABLED {
+			return;
+		}
+
+		let mut arch = Archetype::<()>::new("archetype");
+		let target = arch.spawn("target");
+
+		set_track_dependent_holders(true);
+
+		let mut queue = EventQueue::new();
+		queue.push(target, 1);
+
+		let (_, violation) =
+			crate::testing::expect_violation(ViolationKind::DisconnectedDependents, || {
+				arch.despawn(target)
+			});
+
+		let DebugViolation::DisconnectedDependents { holders, .. } = &violation else {
+			unreachable!();
+		};
+		assert_eq!(holders, &[type_name::<EventQueue<i32>>()]);
+
+		queue.clear();
+	}
+}
@@ -4,6 +4,8 @@ pub mod debug;
 pub mod entity;
 pub mod event;
 pub mod storage;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod universe;
 mod util;
 
@@ -12,12 +14,19 @@ pub use {compost, parking_lot};
 pub mod prelude {
 	pub use crate::{
 		compost::{decompose, Context},
-		debug::{label::NO_LABEL, lifetime::Dependent},
+		debug::{
+			label::{lazy_label, NO_LABEL},
+			lifetime::Dependent,
+		},
 		entity::{
 			bundle, Archetype, ArchetypeId, ArchetypeMap, ArchetypeSet, Bundle, Entity, EntityMap,
 			EntitySet, SingleBundle, SingleEntity, WeakArchetypeId, WeakArchetypeMap,
 		},
-		event::{func, injectors, DestroyQueue, EntityDestroyEvent, EventQueue, EventQueueIter},
+		event::{
+			func, injectors, ConcurrentEventQueue, DestroyQueue, EntityDestroyEvent, EventChannel,
+			EventChannelReader, EventCoalescing, EventQueue, EventQueueIter, EventQueueIterRef,
+			EventScheduler, Signal, SignalConnection, SignalHandler, TaskHandle, TaskQueue,
+		},
 		storage::{Query, Storage, StorageView, StorageViewMut},
 		universe::{BypassExclusivity, ExclusiveUniverse, Universe},
 	};
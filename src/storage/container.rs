@@ -3,7 +3,10 @@ use std::{any::type_name, cell::UnsafeCell, mem, ops};
 use derive_where::derive_where;
 
 use crate::{
-	debug::lifetime::{DebugLifetime, DebugLifetimeWrapper},
+	debug::{
+		lifetime::{DebugLifetime, DebugLifetimeWrapper},
+		violation::{report_violation, DebugViolation},
+	},
 	entity::hashers::ArchetypeBuildHasher,
 	util::{
 		ptr::PointeeCastExt,
@@ -50,7 +53,10 @@ impl<T> Storage<T> {
 
 	pub fn get_run(&self, archetype: ArchetypeId) -> Option<&StorageRun<T>> {
 		if archetype.is_condemned() {
-			log::error!("Acquired the storage run of the dead archetype {archetype:?}.");
+			report_violation(DebugViolation::use_after_free(
+				archetype,
+				format!("Acquired the storage run of the dead archetype {archetype:?}."),
+			));
 			// (fallthrough)
 		}
 
@@ -59,7 +65,10 @@ impl<T> Storage<T> {
 
 	pub fn get_run_mut(&mut self, archetype: ArchetypeId) -> Option<&mut StorageRun<T>> {
 		if archetype.is_condemned() {
-			log::error!("Acquired the storage run of the dead archetype {archetype:?}.");
+			report_violation(DebugViolation::use_after_free(
+				archetype,
+				format!("Acquired the storage run of the dead archetype {archetype:?}."),
+			));
 			// (fallthrough)
 		}
 
@@ -84,7 +93,10 @@ impl<T> Storage<T> {
 
 	pub fn get_or_create_run(&mut self, archetype: ArchetypeId) -> &mut StorageRun<T> {
 		if archetype.is_condemned() {
-			log::error!("Acquired the storage run of the dead archetype {archetype:?}");
+			report_violation(DebugViolation::use_after_free(
+				archetype,
+				format!("Acquired the storage run of the dead archetype {archetype:?}"),
+			));
 			// (fallthrough)
 		}
 
@@ -101,12 +113,16 @@ impl<T> Storage<T> {
 		let run = self.get_or_create_run(entity.archetype);
 
 		if cfg!(debug_assertions) && run.get_slot_by_idx(entity.slot).is_some() {
-			log::warn!(
-				"`.add`'ed a component of type {} to an entity {:?} that already had the component. \
-			     Use `.insert` instead if you wish to replace pre-existing components silently.",
-				type_name::<T>(),
-				entity,
-			);
+			report_violation(DebugViolation::DuplicateComponent {
+				subject: entity.into(),
+				type_name: type_name::<T>(),
+				message: format!(
+					"`.add`'ed a component of type {} to an entity {:?} that already had the component. \
+					 Use `.insert` instead if you wish to replace pre-existing components silently.",
+					type_name::<T>(),
+					entity,
+				),
+			});
 			// (fallthrough)
 		}
 
@@ -115,12 +131,16 @@ impl<T> Storage<T> {
 
 	pub fn try_remove(&mut self, entity: Entity) -> Option<T> {
 		if entity.is_condemned() {
-			log::error!(
-				"Removed a component of type {} from the already-dead entity {:?}. \
-				 Please remove all components from an entity *before* destroying them to avoid UAF bugs.",
-				type_name::<T>(),
+			report_violation(DebugViolation::use_after_free_with_type(
 				entity,
-			);
+				type_name::<T>(),
+				format!(
+					"Removed a component of type {} from the already-dead entity {:?}. \
+				 Please remove all components from an entity *before* destroying them to avoid UAF bugs.",
+					type_name::<T>(),
+					entity,
+				),
+			));
 			// (fallthrough)
 		}
 
@@ -146,22 +166,30 @@ impl<T> Storage<T> {
 	pub fn remove(&mut self, entity: Entity) {
 		let res = self.try_remove(entity);
 		if cfg!(debug_assertions) && res.is_none() {
-			log::warn!(
-				"Removed a component of type {} from entity {:?}, which didn't have that component. \
-				 Use `.try_remove` instead if you wish to ignore removals from entities without the component.",
-				type_name::<T>(),
-				entity,
-			);
+			report_violation(DebugViolation::MissingComponent {
+				subject: entity.into(),
+				type_name: type_name::<T>(),
+				message: format!(
+					"Removed a component of type {} from entity {:?}, which didn't have that component. \
+					 Use `.try_remove` instead if you wish to ignore removals from entities without the component.",
+					type_name::<T>(),
+					entity,
+				),
+			});
 			// (fallthrough)
 		}
 	}
 
 	pub fn get(&self, entity: Entity) -> Option<&T> {
 		if entity.is_condemned() {
-			log::error!(
-				"Fetched component of type {} from the dead entity {entity:?}.",
-				type_name::<T>()
-			);
+			report_violation(DebugViolation::use_after_free_with_type(
+				entity,
+				type_name::<T>(),
+				format!(
+					"Fetched component of type {} from the dead entity {entity:?}.",
+					type_name::<T>()
+				),
+			));
 			// (fallthrough)
 		}
 
@@ -173,10 +201,14 @@ impl<T> Storage<T> {
 
 	pub fn get_mut(&mut self, entity: Entity) -> Option<&mut T> {
 		if entity.is_condemned() {
-			log::error!(
-				"Fetched component of type {} from the dead entity {entity:?}.",
-				type_name::<T>()
-			);
+			report_violation(DebugViolation::use_after_free_with_type(
+				entity,
+				type_name::<T>(),
+				format!(
+					"Fetched component of type {} from the dead entity {entity:?}.",
+					type_name::<T>()
+				),
+			));
 			// (fallthrough)
 		}
 
@@ -194,6 +226,14 @@ impl<T> Storage<T> {
 		self.archetypes.clear();
 	}
 
+	pub fn count_condemned(&self) -> usize {
+		self.archetypes
+			.iter()
+			.flat_map(|(_, run)| run.as_slice())
+			.filter(|slot| slot.pair().is_some_and(|(lt, _)| lt.is_condemned()))
+			.count()
+	}
+
 	pub fn query_in_ref(&self, archetype: ArchetypeId) -> QueryIter<(StorageIterRef<T>,)> {
 		(self,).query_in(archetype)
 	}
@@ -278,12 +318,16 @@ impl<'a, T> StorageRunView<'a, T> {
 			.and_then(|slot| slot.pair());
 
 		if let Some((lt, _)) = slot.filter(|(lt, _)| lt.is_condemned()) {
-			log::error!(
-				"Fetched a storage slot at index {} of type {:?} for the dead entity {:?}",
-				slot_idx,
-				type_name::<T>(),
+			report_violation(DebugViolation::use_after_free_with_type(
 				lt,
-			);
+				type_name::<T>(),
+				format!(
+					"Fetched a storage slot at index {} of type {:?} for the dead entity {:?}",
+					slot_idx,
+					type_name::<T>(),
+					lt,
+				),
+			));
 			// (fallthrough)
 		}
 
@@ -293,20 +337,27 @@ impl<'a, T> StorageRunView<'a, T> {
 	pub fn get_slot(self, entity: Entity) -> Option<(DebugLifetime, &'a T)> {
 		// Validate handle
 		if cfg!(debug_assertions) && entity.archetype != self.archetype {
-			log::error!(
-				"Attempted to get an entity from a different archetype {:?} into a storage run \
-				 for entities of archetype {:?}",
-				entity.archetype,
-				self.archetype,
-			);
+			report_violation(DebugViolation::ArchetypeMismatch {
+				entity,
+				expected: self.archetype,
+				message: format!(
+					"Attempted to get an entity from a different archetype {:?} into a storage run \
+					 for entities of archetype {:?}",
+					entity.archetype, self.archetype,
+				),
+			});
 			// (fallthrough)
 		}
 
 		if entity.is_condemned() {
-			log::error!(
-				"Attempted to get a component of type {:?} from the dead entity {entity:?}",
-				type_name::<T>()
-			);
+			report_violation(DebugViolation::use_after_free_with_type(
+				entity,
+				type_name::<T>(),
+				format!(
+					"Attempted to get a component of type {:?} from the dead entity {entity:?}",
+					type_name::<T>()
+				),
+			));
 			// (fallthrough)
 		}
 
@@ -374,20 +425,27 @@ impl<T> StorageRun<T> {
 	fn insert(&mut self, entity: Entity, value: T) -> (Option<T>, &mut T) {
 		// Validate handles
 		if cfg!(debug_assertions) && entity.archetype != self.archetype {
-			log::error!(
-				"Attempted to insert an entity from a different archetype {:?} into a storage run \
-				 for entities of archetype {:?}",
-				entity.archetype,
-				self.archetype,
-			);
+			report_violation(DebugViolation::ArchetypeMismatch {
+				entity,
+				expected: self.archetype,
+				message: format!(
+					"Attempted to insert an entity from a different archetype {:?} into a storage run \
+					 for entities of archetype {:?}",
+					entity.archetype, self.archetype,
+				),
+			});
 			// (fallthrough)
 		}
 
 		if entity.is_condemned() {
-			log::error!(
-				"Attempted to attach a component of type {:?} to the dead entity {entity:?}",
-				type_name::<T>()
-			);
+			report_violation(DebugViolation::use_after_free_with_type(
+				entity,
+				type_name::<T>(),
+				format!(
+					"Attempted to attach a component of type {:?} to the dead entity {entity:?}",
+					type_name::<T>()
+				),
+			));
 			// (fallthrough)
 		}
 
@@ -404,7 +462,7 @@ impl<T> StorageRun<T> {
 		let replaced = mem::replace(
 			slot,
 			StorageSlot::Full {
-				lifetime: Dependent::new(entity.lifetime),
+				lifetime: Dependent::new_named(entity.lifetime, type_name::<Storage<T>>()),
 				value,
 			},
 		);
@@ -458,12 +516,16 @@ impl<T> StorageRun<T> {
 			.and_then(|slot| slot.pair_mut());
 
 		if let Some((lt, _)) = slot.as_ref().filter(|(lt, _)| lt.is_condemned()) {
-			log::error!(
-				"Fetched a storage slot at index {} of type {:?} for the dead entity {:?}",
-				slot_idx,
+			report_violation(DebugViolation::use_after_free_with_type(
+				*lt,
 				type_name::<T>(),
-				lt,
-			);
+				format!(
+					"Fetched a storage slot at index {} of type {:?} for the dead entity {:?}",
+					slot_idx,
+					type_name::<T>(),
+					lt,
+				),
+			));
 			// (fallthrough)
 		}
 
@@ -473,20 +535,27 @@ impl<T> StorageRun<T> {
 	pub fn get_slot_mut(&mut self, entity: Entity) -> Option<(DebugLifetime, &mut T)> {
 		// Validate handle
 		if cfg!(debug_assertions) && entity.archetype != self.archetype {
-			log::error!(
-				"Attempted to get an entity from a different archetype {:?} into a storage run \
-				 for entities of archetype {:?}",
-				entity.archetype,
-				self.archetype,
-			);
+			report_violation(DebugViolation::ArchetypeMismatch {
+				entity,
+				expected: self.archetype,
+				message: format!(
+					"Attempted to get an entity from a different archetype {:?} into a storage run \
+					 for entities of archetype {:?}",
+					entity.archetype, self.archetype,
+				),
+			});
 			// (fallthrough)
 		}
 
 		if entity.is_condemned() {
-			log::error!(
-				"Attempted to get a component of type {:?} from the dead entity {entity:?}",
-				type_name::<T>()
-			);
+			report_violation(DebugViolation::use_after_free_with_type(
+				entity,
+				type_name::<T>(),
+				format!(
+					"Attempted to get a component of type {:?} from the dead entity {entity:?}",
+					type_name::<T>()
+				),
+			));
 			// (fallthrough)
 		}
 
@@ -601,3 +670,56 @@ impl<T> StorageSlot<T> {
 		}
 	}
 }
+
+#[cfg(all(test, debug_assertions))]
+mod tests {
+	use crate::{debug::violation::ViolationSubject, testing::capture_violations, Archetype};
+
+	use super::*;
+
+	#[test]
+	fn misuse_is_reported_as_violations() {
+		let mut arch = Archetype::<()>::new("archetype");
+		let target = arch.spawn("target");
+		let mut storage = Storage::new();
+
+		let (_, violations) = capture_violations(|| {
+			storage.add(target, 1u32);
+			storage.add(target, 2u32);
+			storage.remove(target);
+			storage.remove(target);
+		});
+
+		assert_eq!(violations.len(), 2, "{violations:?}");
+
+		for violation in violations.iter() {
+			let (DebugViolation::DuplicateComponent {
+				subject: ViolationSubject::Entity(subject),
+				type_name,
+				..
+			}
+			| DebugViolation::MissingComponent {
+				subject: ViolationSubject::Entity(subject),
+				type_name,
+				..
+			}) = violation
+			else {
+				panic!("unexpected violation: {violation:?}");
+			};
+
+			assert_eq!(*subject, target);
+			assert_eq!(*type_name, "u32");
+		}
+
+		assert!(matches!(
+			violations[0],
+			DebugViolation::DuplicateComponent { .. }
+		));
+		assert!(matches!(
+			violations[1],
+			DebugViolation::MissingComponent { .. }
+		));
+
+		arch.despawn(target);
+	}
+}
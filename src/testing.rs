@@ -0,0 +1,197 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::debug::violation::{
+	report_violation, set_thread_violation_policy, DebugViolation, ViolationKind, ViolationPolicy,
+};
+
+// === Capture === //
+
+// Violations reported by other threads are not captured and go through their regular policy.
+pub fn capture_violations<R>(f: impl FnOnce() -> R) -> (R, Vec<DebugViolation>) {
+	struct RestoreGuard(Option<Option<ViolationPolicy>>);
+
+	impl Drop for RestoreGuard {
+		fn drop(&mut self) {
+			set_thread_violation_policy(self.0.take().unwrap());
+		}
+	}
+
+	let captured = Arc::new(Mutex::new(Vec::new()));
+	let res = {
+		let captured = captured.clone();
+		let _guard = RestoreGuard(Some(set_thread_violation_policy(Some(
+			ViolationPolicy::hook(move |violation| captured.lock().push(violation.clone())),
+		))));
+
+		f()
+	};
+
+	let captured = std::mem::take(&mut *captured.lock());
+	(res, captured)
+}
+
+// === Assertions === //
+
+#[track_caller]
+pub fn assert_no_violations<R>(f: impl FnOnce() -> R) -> R {
+	let (res, violations) = capture_violations(f);
+	assert_violations_empty(violations, |_| true);
+	res
+}
+
+#[track_caller]
+pub fn assert_no_uaf<R>(f: impl FnOnce() -> R) -> R {
+	let (res, violations) = capture_violations(f);
+	assert_violations_empty(violations, |violation| {
+		violation.kind() == ViolationKind::UseAfterFree
+	});
+	res
+}
+
+#[track_caller]
+pub fn expect_violation<R>(kind: ViolationKind, f: impl FnOnce() -> R) -> (R, DebugViolation) {
+	let (res, mut violations) = capture_violations(f);
+
+	let Some(index) = violations
+		.iter()
+		.position(|violation| violation.kind() == kind)
+	else {
+		panic!(
+			"Expected a {kind:?} violation but got: {}",
+			fmt_violation_list(&violations, "none"),
+		);
+	};
+
+	let violation = violations.remove(index);
+	reraise_violations(violations);
+
+	(res, violation)
+}
+
+#[track_caller]
+fn assert_violations_empty(
+	violations: Vec<DebugViolation>,
+	mut is_relevant: impl FnMut(&DebugViolation) -> bool,
+) {
+	let (relevant, irrelevant) = violations
+		.into_iter()
+		.partition::<Vec<_>, _>(&mut is_relevant);
+
+	// Violations we weren't asked about still deserve to be reported.
+	reraise_violations(irrelevant);
+
+	if !relevant.is_empty() {
+		panic!(
+			"Unexpected violations were reported:{}",
+			fmt_violation_list(&relevant, ""),
+		);
+	}
+}
+
+fn reraise_violations(violations: Vec<DebugViolation>) {
+	for violation in violations {
+		report_violation(violation);
+	}
+}
+
+fn fmt_violation_list(violations: &[DebugViolation], if_empty: &str) -> String {
+	if violations.is_empty() {
+		return if_empty.to_string();
+	}
+
+	violations
+		.iter()
+		.map(|violation| format!("\n- {:?}: {violation}", violation.kind()))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::panic::{catch_unwind, AssertUnwindSafe};
+
+	use crate::debug::lifetime::Lifetime;
+
+	use super::*;
+
+	fn report_uaf() {
+		let lifetime = Lifetime::new("uaf");
+		lifetime.destroy();
+
+		report_violation(DebugViolation::use_after_free(
+			lifetime,
+			String::from("uaf"),
+		));
+	}
+
+	fn report_leak() {
+		report_violation(DebugViolation::Leak {
+			type_name: "leak",
+			count: 1,
+			message: String::from("leak"),
+		});
+	}
+
+	#[test]
+	fn captures_and_restores_policy() {
+		let (res, violations) = capture_violations(|| {
+			report_uaf();
+			report_leak();
+			4
+		});
+
+		assert_eq!(res, 4);
+		assert_eq!(
+			violations
+				.iter()
+				.map(DebugViolation::kind)
+				.collect::<Vec<_>>(),
+			[ViolationKind::UseAfterFree, ViolationKind::Leak],
+		);
+
+		// The previous policy is restored even if the closure panics.
+		let prev_policy = set_thread_violation_policy(Some(ViolationPolicy::Panic));
+		let _ = catch_unwind(|| capture_violations(|| panic!("oops")));
+		assert!(catch_unwind(report_leak).is_err());
+		set_thread_violation_policy(prev_policy);
+	}
+
+	#[test]
+	fn assertions_filter_by_kind() {
+		assert_eq!(assert_no_violations(|| 1), 1);
+		assert!(catch_unwind(|| assert_no_violations(report_leak)).is_err());
+		assert!(catch_unwind(|| assert_no_uaf(report_uaf)).is_err());
+
+		// Irrelevant violations are re-raised through the outer policy.
+		let (_, outer) = capture_violations(|| assert_no_uaf(report_leak));
+		assert_eq!(outer.len(), 1);
+		assert_eq!(outer[0].kind(), ViolationKind::Leak);
+	}
+
+	#[test]
+	fn expect_violation_removes_one_match() {
+		let (_, outer) = capture_violations(|| {
+			let (res, violation) = expect_violation(ViolationKind::UseAfterFree, || {
+				report_leak();
+				report_uaf();
+				5
+			});
+
+			assert_eq!(res, 5);
+			assert_eq!(violation.kind(), ViolationKind::UseAfterFree);
+		});
+
+		assert_eq!(outer.len(), 1);
+		assert_eq!(outer[0].kind(), ViolationKind::Leak);
+
+		let err = catch_unwind(AssertUnwindSafe(|| {
+			expect_violation(ViolationKind::DisconnectedDependents, report_leak)
+		}))
+		.unwrap_err();
+		assert!(err
+			.downcast_ref::<String>()
+			.unwrap()
+			.starts_with("Expected a DisconnectedDependents violation"));
+	}
+}
@@ -1,5 +1,7 @@
 use std::{
 	any::{type_name, Any},
+	error::Error,
+	fmt,
 	marker::PhantomData,
 	mem::{self, transmute},
 	ops::Deref,
@@ -7,9 +9,10 @@ use std::{
 		atomic::{AtomicBool, Ordering::Relaxed},
 		Arc, Weak,
 	},
+	time::{Duration, Instant},
 };
 
-use fnv::FnvBuildHasher;
+use fnv::{FnvBuildHasher, FnvHashMap};
 use parking_lot::{
 	MappedMutexGuard, MappedRwLockReadGuard, MappedRwLockWriteGuard, Mutex, MutexGuard, RwLock,
 	RwLockReadGuard, RwLockWriteGuard,
@@ -17,23 +20,33 @@ use parking_lot::{
 
 use crate::{
 	debug::{
+		borrow::{BorrowConflict, BorrowLocation, BorrowTracker},
 		label::DebugLabel,
-		lifetime::{DebugLifetimeWrapper, Lifetime},
+		lifetime::{DebugLifetimeWrapper, Lifetime, LifetimeName},
+		violation::{report_violation, DebugViolation, ViolationSubject},
 	},
 	entity::{hashers, WeakArchetypeId},
+	event::{ConcurrentEventQueue, EventChannel, EventScheduler, Signal},
 	func,
-	util::{eventual_map::EventualMap, type_id::NamedTypeId},
+	util::eventual_map::EventualMap,
 	Archetype, ArchetypeId, Bundle, Entity, SingleBundle, SingleEntity, Storage,
 };
 
+pub use crate::util::type_id::NamedTypeId;
+
 // === Universe === //
 
+const UNFLUSHED_WARN_THRESHOLD: usize = 1024;
+const FLUSH_TASK_LINGER_WARN_THRESHOLD: Duration = Duration::from_secs(10);
+
 #[derive(Debug, Default)]
 pub struct Universe {
 	resources: EventualMap<NamedTypeId, dyn Any + Send + Sync, FnvBuildHasher>,
 	archetypes: EventualMap<ArchetypeId, ManagedArchetype, hashers::ArchetypeBuildHasher>,
 	needs_flushing: Mutex<Vec<WeakArchetypeId>>,
 	proxied: Arc<ProxyState>,
+	borrows: BorrowTracker,
+	storage_leak_checks: Mutex<FnvHashMap<NamedTypeId, (&'static str, fn(&Universe) -> usize)>>,
 }
 
 #[derive(Debug)]
@@ -46,7 +59,55 @@ struct ManagedArchetype {
 
 #[derive(Debug, Default)]
 struct ProxyState {
-	flush_tasks: Mutex<Vec<UniverseFlushTask>>,
+	flush_tasks: Mutex<PendingFlushTasks>,
+	warned_unflushed: AtomicBool,
+}
+
+#[derive(Debug, Default)]
+struct PendingFlushTasks {
+	tasks: Vec<UniverseFlushTask>,
+	since: Option<Instant>,
+}
+
+impl ProxyState {
+	fn push_flush_task(&self, task: UniverseFlushTask) {
+		let mut pending = self.flush_tasks.lock();
+		pending.since.get_or_insert_with(Instant::now);
+		pending.tasks.push(task);
+		let count = pending.tasks.len();
+		drop(pending);
+
+		if count >= UNFLUSHED_WARN_THRESHOLD {
+			self.warn_unflushed(count, None, format_args!("{count} flush tasks are"));
+		} else if let Some(pending_for) = self.lingering_tasks_pending_for() {
+			self.warn_unflushed(
+				count,
+				Some(pending_for),
+				format_args!(
+					"{count} flush task{} have been pending for {pending_for:?} and are",
+					if count == 1 { "" } else { "s" },
+				),
+			);
+		}
+	}
+
+	fn lingering_tasks_pending_for(&self) -> Option<Duration> {
+		let pending_for = self.flush_tasks.lock().since?.elapsed();
+		(pending_for >= FLUSH_TASK_LINGER_WARN_THRESHOLD).then_some(pending_for)
+	}
+
+	fn warn_unflushed(&self, count: usize, pending_for: Option<Duration>, what: fmt::Arguments) {
+		// We only warn once between flushes to avoid spamming the logs.
+		if !self.warned_unflushed.swap(true, Relaxed) {
+			report_violation(DebugViolation::UnflushedUniverse {
+				count,
+				pending_for,
+				message: format!(
+					"{what} waiting on `Universe::flush`. Did you forget to flush the universe?"
+				),
+			});
+		}
+	}
 }
 
 impl Universe {
@@ -65,17 +126,22 @@ impl Universe {
 	// === Resource Primitives === //
 
 	pub fn init_resource<T: 'static + Send + Sync>(&self, value: T) -> &T {
-		self.resources
+		let value = self
+			.resources
 			.add(NamedTypeId::of::<T>(), Box::new(value))
 			.downcast_ref()
-			.unwrap()
+			.unwrap();
+
+		self.warn_if_unflushed();
+		value
 	}
 
 	pub fn unload_resource<T: 'static>(&mut self) -> Option<Box<T>> {
 		self.flush();
-		self.resources
-			.remove(&NamedTypeId::of::<T>())
-			.map(|v| v.downcast().ok().unwrap())
+		self.resources.remove(&NamedTypeId::of::<T>()).map(|v| {
+			self.borrows.forget(&*v);
+			v.downcast().ok().unwrap()
+		})
 	}
 
 	pub fn try_resource<T: 'static>(&self) -> Option<&T> {
@@ -89,10 +155,21 @@ impl Universe {
 		T: 'static + Send + Sync,
 		F: FnOnce() -> T,
 	{
-		self.resources
-			.get_or_create(NamedTypeId::of::<T>(), || Box::new(init()))
+		let mut created = false;
+		let value = self
+			.resources
+			.get_or_create(NamedTypeId::of::<T>(), || {
+				created = true;
+				Box::new(init())
+			})
 			.downcast_ref()
-			.unwrap()
+			.unwrap();
+
+		if created {
+			self.warn_if_unflushed();
+		}
+
+		value
 	}
 
 	pub fn resource_or_panic<T: 'static>(&self) -> &T {
@@ -109,28 +186,91 @@ impl Universe {
 		self.resource()
 	}
 
+	#[track_caller]
+	pub fn try_resource_ref<T: BuildableResourceRw>(
+		&self,
+	) -> Result<RwLockReadGuard<T>, UniverseError> {
+		self.borrows
+			.try_read(self.resource_rw())
+			.map_err(UniverseError::already_borrowed::<T>)
+	}
+
+	#[track_caller]
+	pub fn try_resource_mut<T: BuildableResourceRw>(
+		&self,
+	) -> Result<RwLockWriteGuard<T>, UniverseError> {
+		self.borrows
+			.try_write(self.resource_rw())
+			.map_err(UniverseError::already_borrowed::<T>)
+	}
+
+	#[track_caller]
 	pub fn resource_ref<T: BuildableResourceRw>(&self) -> RwLockReadGuard<T> {
-		self.resource_rw().try_read().unwrap()
+		UniverseError::unwrap(self.try_resource_ref())
 	}
 
+	#[track_caller]
 	pub fn resource_mut<T: BuildableResourceRw>(&self) -> RwLockWriteGuard<T> {
-		self.resource_rw().try_write().unwrap()
+		UniverseError::unwrap(self.try_resource_mut())
+	}
+
+	#[track_caller]
+	pub fn try_storage<T: 'static + Send + Sync>(
+		&self,
+	) -> Result<RwLockReadGuard<Storage<T>>, UniverseError> {
+		self.try_resource_ref()
+	}
+
+	#[track_caller]
+	pub fn try_storage_mut<T: 'static + Send + Sync>(
+		&self,
+	) -> Result<RwLockWriteGuard<Storage<T>>, UniverseError> {
+		self.try_resource_mut()
 	}
 
+	#[track_caller]
 	pub fn storage<T: 'static + Send + Sync>(&self) -> RwLockReadGuard<Storage<T>> {
 		self.resource_ref()
 	}
 
+	#[track_caller]
 	pub fn storage_mut<T: 'static + Send + Sync>(&self) -> RwLockWriteGuard<Storage<T>> {
 		self.resource_mut()
 	}
 
+	#[track_caller]
+	pub fn try_comp<T: 'static + Send + Sync>(
+		&self,
+		target: Entity,
+	) -> Result<MappedRwLockReadGuard<T>, UniverseError> {
+		RwLockReadGuard::try_map(self.try_storage()?, |storage| storage.get(target)).map_err(|_| {
+			UniverseError::MissingComponent {
+				entity: target,
+				type_name: type_name::<T>(),
+			}
+		})
+	}
+
+	#[track_caller]
+	pub fn try_comp_mut<T: 'static + Send + Sync>(
+		&self,
+		target: Entity,
+	) -> Result<MappedRwLockWriteGuard<T>, UniverseError> {
+		RwLockWriteGuard::try_map(self.try_storage_mut()?, |storage| storage.get_mut(target))
+			.map_err(|_| UniverseError::MissingComponent {
+				entity: target,
+				type_name: type_name::<T>(),
+			})
+	}
+
+	#[track_caller]
 	pub fn comp<T: 'static + Send + Sync>(&self, target: Entity) -> MappedRwLockReadGuard<T> {
-		RwLockReadGuard::map(self.storage(), |storage| &storage[target])
+		UniverseError::unwrap(self.try_comp(target))
 	}
 
+	#[track_caller]
 	pub fn comp_mut<T: 'static + Send + Sync>(&self, target: Entity) -> MappedRwLockWriteGuard<T> {
-		RwLockWriteGuard::map(self.storage_mut(), |storage| &mut storage[target])
+		UniverseError::unwrap(self.try_comp_mut(target))
 	}
 
 	// === Archetype Management === //
@@ -148,6 +288,7 @@ impl Universe {
 				needs_flushing: AtomicBool::new(false),
 			}),
 		);
+		self.warn_if_unflushed();
 
 		ArchetypeHandle {
 			_ty: PhantomData,
@@ -160,20 +301,22 @@ impl Universe {
 		self.register_archetype(Archetype::new(name))
 	}
 
-	pub fn try_archetype_by_id(&self, id: ArchetypeId) -> Option<MutexGuard<Archetype>> {
-		if id.is_condemned() {
-			log::error!("Acquired a dead archetype with ID {id:?} from the universe.");
-			// (fallthrough)
-		}
-
-		self.archetypes
-			.get(&id)
-			.map(|arch| arch.archetype.try_lock().unwrap())
+	#[track_caller]
+	pub fn try_archetype_by_id(
+		&self,
+		id: ArchetypeId,
+	) -> Result<MutexGuard<Archetype>, UniverseError> {
+		self.borrows
+			.try_lock(&self.try_managed_archetype(id)?.archetype)
+			.map_err(UniverseError::already_borrowed::<Archetype>)
 	}
 
 	pub fn weak_archetype_id_for(&self, id: ArchetypeId) -> WeakArchetypeId {
 		if id.is_condemned() {
-			log::error!("Upgraded a dead archetype ID {id:?} to a weak archetype ID.");
+			report_violation(DebugViolation::use_after_free(
+				id,
+				format!("Upgraded a dead archetype ID {id:?} to a weak archetype ID."),
+			));
 			// (fallthrough)
 		}
 
@@ -183,23 +326,75 @@ impl Universe {
 		}
 	}
 
+	#[track_caller]
 	pub fn archetype_by_id(&self, id: ArchetypeId) -> MutexGuard<Archetype> {
-		self.try_archetype_by_id(id).unwrap()
+		UniverseError::unwrap(self.try_archetype_by_id(id))
+	}
+
+	fn try_managed_archetype(&self, id: ArchetypeId) -> Result<&ManagedArchetype, UniverseError> {
+		if id.is_condemned() {
+			return Err(UniverseError::DeadArchetype(id));
+		}
+
+		self.archetypes
+			.get(&id)
+			.ok_or(UniverseError::MissingArchetype(id))
+	}
+
+	pub fn archetypes(&self) -> impl Iterator<Item = ArchetypeInfo> + '_ {
+		self.archetypes
+			.entries()
+			.into_iter()
+			.map(|(id, arch)| ArchetypeInfo {
+				id,
+				weak_id: WeakArchetypeId {
+					lifetime: arch.lifetime,
+					id: id.id,
+				},
+				label: arch.lifetime.debug_name(),
+				len: arch.archetype.try_lock().map(|arch| arch.len()),
+			})
+	}
+
+	pub fn archetypes_by_label<'a>(
+		&'a self,
+		label: &'a str,
+	) -> impl Iterator<Item = ArchetypeInfo> + 'a {
+		self.archetypes()
+			.filter(move |info| info.label.to_string() == label)
+	}
+
+	pub fn archetypes_with_meta<T: 'static>(&self) -> impl Iterator<Item = (ArchetypeInfo, &T)> {
+		self.archetypes().filter_map(|info| {
+			let meta = self.archetypes[&info.id]
+				.meta
+				.get(&NamedTypeId::of::<T>())?
+				.downcast_ref()
+				.unwrap();
+
+			Some((info, meta))
+		})
 	}
 
 	pub fn remove_archetype(&mut self, id: ArchetypeId) -> Archetype {
 		if id.is_condemned() {
-			log::error!("Removed a dead archetype with ID {id:?} from the universe.");
+			report_violation(DebugViolation::use_after_free(
+				id,
+				format!("Removed a dead archetype with ID {id:?} from the universe."),
+			));
 			// (fallthrough)
 		}
 
-		self.archetypes.remove(&id).unwrap().archetype.into_inner()
+		let arch = self.archetypes.remove(&id).unwrap();
+		self.borrows.forget(&arch.archetype);
+		arch.archetype.into_inner()
 	}
 
 	pub fn archetype_handle<M: ?Sized + BuildableArchetype>(&self) -> &ArchetypeHandle<M> {
 		self.resource()
 	}
 
+	#[track_caller]
 	pub fn archetype<M: ?Sized + BuildableArchetype>(&self) -> MappedMutexGuard<Archetype<M>> {
 		MutexGuard::map(
 			self.archetype_by_id(self.archetype_handle::<M>().id()),
@@ -212,7 +407,10 @@ impl Universe {
 	pub fn annotate_archetype<T: 'static + Send + Sync>(&self, id: ArchetypeId, data: T) -> &T {
 		// Validate ID
 		if id.is_condemned() {
-			log::error!("Annotated a dead archetype with ID {id:?} in the universe.");
+			report_violation(DebugViolation::use_after_free(
+				id,
+				format!("Annotated a dead archetype with ID {id:?} in the universe."),
+			));
 			// (fallthrough)
 		}
 
@@ -228,6 +426,7 @@ impl Universe {
 				lifetime: arch.lifetime,
 				id: id.id,
 			});
+			self.warn_if_unflushed();
 		}
 
 		// Write metadata
@@ -237,24 +436,71 @@ impl Universe {
 			.unwrap()
 	}
 
-	pub fn try_archetype_meta<T: 'static>(&self, id: ArchetypeId) -> Option<&T> {
+	pub fn try_archetype_meta<T: 'static>(&self, id: ArchetypeId) -> Result<&T, UniverseError> {
+		self.try_managed_archetype(id)?
+			.meta
+			.get(&NamedTypeId::of::<T>())
+			.map(|v| v.downcast_ref().unwrap())
+			.ok_or(UniverseError::MissingArchetypeMeta {
+				archetype: id,
+				type_name: type_name::<T>(),
+			})
+	}
+
+	#[track_caller]
+	pub fn archetype_meta<T: 'static>(&self, id: ArchetypeId) -> &T {
+		UniverseError::unwrap(self.try_archetype_meta(id))
+	}
+
+	pub fn replace_archetype_meta<T: 'static + Send + Sync>(
+		&mut self,
+		id: ArchetypeId,
+		data: T,
+	) -> Option<T> {
 		if id.is_condemned() {
-			log::error!("Acquired metadata from a dead archetype with ID {id:?} in the universe.");
+			report_violation(DebugViolation::use_after_free(
+				id,
+				format!("Replaced metadata on a dead archetype with ID {id:?} in the universe."),
+			));
 			// (fallthrough)
 		}
 
 		self.archetypes[&id]
 			.meta
-			.get(&NamedTypeId::of::<T>())
-			.map(|v| v.downcast_ref().unwrap())
+			.insert(NamedTypeId::of::<T>(), Box::new(data))
+			.map(|v| *v.downcast().ok().unwrap())
 	}
 
-	pub fn archetype_meta<T: 'static>(&self, id: ArchetypeId) -> &T {
-		self.try_archetype_meta(id).unwrap()
+	pub fn remove_archetype_meta<T: 'static>(&mut self, id: ArchetypeId) -> Option<T> {
+		if id.is_condemned() {
+			report_violation(DebugViolation::use_after_free(
+				id,
+				format!("Removed metadata from a dead archetype with ID {id:?} in the universe."),
+			));
+			// (fallthrough)
+		}
+
+		self.archetypes[&id]
+			.meta
+			.remove(&NamedTypeId::of::<T>())
+			.map(|v| *v.downcast().ok().unwrap())
+	}
+
+	pub fn archetype_meta_types(&self, id: ArchetypeId) -> Vec<NamedTypeId> {
+		if id.is_condemned() {
+			report_violation(DebugViolation::use_after_free(
+				id,
+				format!("Enumerated metadata of a dead archetype with ID {id:?} in the universe."),
+			));
+			// (fallthrough)
+		}
+
+		self.archetypes[&id].meta.keys()
 	}
 
 	// === Exclusive Helpers === //
 
+	#[track_caller]
 	pub fn spawn_bundled<B: BuildableArchetype + Bundle>(
 		&mut self,
 		name: impl DebugLabel,
@@ -263,6 +509,7 @@ impl Universe {
 		self.as_exclusive().spawn_bundled(name, bundle)
 	}
 
+	#[track_caller]
 	pub fn despawn_bundled<B: BuildableArchetype + Bundle>(&mut self, target: Entity) -> B {
 		self.as_exclusive().despawn_bundled(target)
 	}
@@ -270,7 +517,7 @@ impl Universe {
 	// === Flushing === //
 
 	pub fn add_flush_task(&self, task: UniverseFlushTask) {
-		self.proxied.flush_tasks.lock().push(task);
+		self.proxied.push_flush_task(task);
 	}
 
 	pub fn proxy(&self) -> UniverseProxy {
@@ -278,6 +525,24 @@ impl Universe {
 	}
 
 	pub fn flush(&mut self) {
+		// Tasks which were pushed once and never again can't be caught while pushing so we also
+		// check whether they've lingered here, unless we already warned about them.
+		if !self.proxied.warned_unflushed.swap(false, Relaxed) {
+			if let Some(pending_for) = self.proxied.lingering_tasks_pending_for() {
+				let count = self.proxied.flush_tasks.lock().tasks.len();
+
+				report_violation(DebugViolation::UnflushedUniverse {
+					count,
+					pending_for: Some(pending_for),
+					message: format!(
+						"{count} flush task{} waited {pending_for:?} on `Universe::flush`. Is the \
+						 universe being flushed often enough?",
+						if count == 1 { "" } else { "s" },
+					),
+				});
+			}
+		}
+
 		// Flush maps
 		self.resources.flush();
 		self.archetypes.flush();
@@ -294,11 +559,212 @@ impl Universe {
 		}
 
 		// Process handlers
-		let task_list = mem::take(&mut *self.proxied.flush_tasks.lock());
-		for handler in task_list {
+		let pending = mem::take(&mut *self.proxied.flush_tasks.lock());
+		for handler in pending.tasks {
 			handler(self);
 		}
 	}
+
+	fn warn_if_unflushed(&self) {
+		let nursery_len = self.resources.nursery_len() + self.archetypes.nursery_len();
+		if nursery_len >= UNFLUSHED_WARN_THRESHOLD {
+			self.proxied.warn_unflushed(
+				nursery_len,
+				None,
+				format_args!("{nursery_len} new resources and archetypes are"),
+			);
+		}
+
+		let needs_flushing = self.needs_flushing.lock().len();
+		if needs_flushing >= UNFLUSHED_WARN_THRESHOLD {
+			self.proxied.warn_unflushed(
+				needs_flushing,
+				None,
+				format_args!("{needs_flushing} archetypes with new metadata are"),
+			);
+		}
+	}
+
+	// === Leak Checks === //
+
+	fn add_storage_leak_check<T: 'static + Send + Sync>(&self) {
+		fn count_condemned<T: 'static + Send + Sync>(universe: &Universe) -> usize {
+			universe
+				.try_resource::<RwLock<Storage<T>>>()
+				.map_or(0, |storage| storage.read().count_condemned())
+		}
+
+		// Storages can be unloaded and recreated so we make sure to only check each one once.
+		self.storage_leak_checks
+			.lock()
+			.entry(NamedTypeId::of::<Storage<T>>())
+			.or_insert((type_name::<Storage<T>>(), count_condemned::<T>));
+	}
+}
+
+impl Drop for Universe {
+	fn drop(&mut self) {
+		let task_count = self.proxied.flush_tasks.lock().tasks.len();
+		if task_count > 0 {
+			report_violation(DebugViolation::Leak {
+				type_name: type_name::<UniverseFlushTask>(),
+				count: task_count,
+				message: format!(
+					"Dropped a `Universe` with {task_count} flush task{} which never ran. \
+					 Did you forget to flush the universe?",
+					if task_count == 1 { "" } else { "s" },
+				),
+			});
+		}
+
+		let archetypes = self
+			.archetypes()
+			.filter_map(|info| Some((info.label, info.len?)))
+			.filter(|(_, len)| *len > 0)
+			.collect::<Vec<_>>();
+
+		if !archetypes.is_empty() {
+			report_violation(DebugViolation::Leak {
+				type_name: type_name::<Entity>(),
+				count: archetypes.iter().map(|(_, len)| len).sum(),
+				message: format!(
+					"Dropped a `Universe` whose archetypes still had living entities: {}.",
+					archetypes
+						.iter()
+						.map(|(label, len)| format!("{label:?} ({len})"))
+						.collect::<Vec<_>>()
+						.join(", "),
+				),
+			});
+		}
+
+		for (type_name, count_condemned) in
+			mem::take(self.storage_leak_checks.get_mut()).into_values()
+		{
+			let count = count_condemned(self);
+			if count > 0 {
+				report_violation(DebugViolation::Leak {
+					type_name,
+					count,
+					message: format!(
+						"Dropped a `Universe` whose {type_name} still held {count} component{} of \
+						 despawned entities.",
+						if count == 1 { "" } else { "s" },
+					),
+				});
+			}
+		}
+	}
+}
+
+// === ArchetypeInfo === //
+
+#[derive(Debug, Copy, Clone)]
+pub struct ArchetypeInfo {
+	pub id: ArchetypeId,
+	pub weak_id: WeakArchetypeId,
+	pub label: LifetimeName,
+	// (`None` if the archetype is currently borrowed)
+	pub len: Option<usize>,
+}
+
+// === UniverseError === //
+
+#[derive(Debug, Clone)]
+pub enum UniverseError {
+	AlreadyBorrowed {
+		type_name: &'static str,
+		mutably: bool,
+		recent_holders: Vec<BorrowLocation>,
+	},
+	MissingArchetype(ArchetypeId),
+	DeadArchetype(ArchetypeId),
+	MissingArchetypeMeta {
+		archetype: ArchetypeId,
+		type_name: &'static str,
+	},
+	MissingComponent {
+		entity: Entity,
+		type_name: &'static str,
+	},
+}
+
+impl fmt::Display for UniverseError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			UniverseError::AlreadyBorrowed {
+				type_name,
+				mutably,
+				recent_holders,
+			} => {
+				write!(
+					f,
+					"{type_name} is already {} borrowed",
+					if *mutably { "mutably" } else { "immutably" },
+				)?;
+
+				// The tracker can't tell which of these are still alive.
+				if !recent_holders.is_empty() {
+					write!(
+						f,
+						" (possibly by the borrow{} at ",
+						if recent_holders.len() == 1 { "" } else { "s" },
+					)?;
+
+					for (i, holder) in recent_holders.iter().rev().enumerate() {
+						if i > 0 {
+							f.write_str(", ")?;
+						}
+						write!(f, "{}:{}", holder.file(), holder.line())?;
+					}
+
+					f.write_str(")")?;
+				}
+
+				Ok(())
+			}
+			UniverseError::MissingArchetype(id) => {
+				write!(
+					f,
+					"archetype with ID {id:?} is not registered in the universe"
+				)
+			}
+			UniverseError::DeadArchetype(id) => {
+				write!(f, "archetype with ID {id:?} is dead")
+			}
+			UniverseError::MissingArchetypeMeta {
+				archetype,
+				type_name,
+			} => write!(
+				f,
+				"archetype with ID {archetype:?} has no metadata of type {type_name}"
+			),
+			UniverseError::MissingComponent { entity, type_name } => write!(
+				f,
+				"failed to find component of type {type_name} for entity {entity:?}"
+			),
+		}
+	}
+}
+
+impl Error for UniverseError {}
+
+impl UniverseError {
+	fn already_borrowed<T: ?Sized>(conflict: BorrowConflict) -> Self {
+		Self::AlreadyBorrowed {
+			type_name: type_name::<T>(),
+			mutably: conflict.mutably,
+			recent_holders: conflict.recent_holders,
+		}
+	}
+
+	#[track_caller]
+	fn unwrap<T>(result: Result<T, Self>) -> T {
+		match result {
+			Ok(value) => value,
+			Err(err) => panic!("{err}"),
+		}
+	}
 }
 
 // === Delegates === //
@@ -336,11 +802,36 @@ impl<M: ?Sized + BuildableArchetype> BuildableResource for ArchetypeHandle<M> {
 }
 
 impl<T: 'static + Send + Sync> BuildableResourceRw for Storage<T> {
-	fn create(_universe: &Universe) -> Self {
+	fn create(universe: &Universe) -> Self {
+		universe.add_storage_leak_check::<T>();
 		Storage::new()
 	}
 }
 
+impl<E: 'static + Send + Sync> BuildableResourceRw for ConcurrentEventQueue<E> {
+	fn create(_universe: &Universe) -> Self {
+		ConcurrentEventQueue::new()
+	}
+}
+
+impl<E: 'static + Send + Sync> BuildableResourceRw for Signal<E> {
+	fn create(_universe: &Universe) -> Self {
+		Signal::new()
+	}
+}
+
+impl<E: 'static + Send + Sync> BuildableResourceRw for EventScheduler<E> {
+	fn create(_universe: &Universe) -> Self {
+		EventScheduler::new()
+	}
+}
+
+impl<E: 'static + Send + Sync> BuildableResourceRw for EventChannel<E> {
+	fn create(_universe: &Universe) -> Self {
+		EventChannel::new()
+	}
+}
+
 // === UniverseProxy === //
 
 #[derive(Debug, Clone)]
@@ -349,11 +840,16 @@ pub struct UniverseProxy(Weak<ProxyState>);
 impl UniverseProxy {
 	pub fn add_flush_task(&self, task: UniverseFlushTask) {
 		let Some(proxy_state) = Weak::upgrade(&self.0) else {
-			log::error!("Attempted to call `add_flush_task` on a `UniverseProxy` belonging to a dead universe.");
+			report_violation(DebugViolation::use_after_free(
+				ViolationSubject::None,
+				String::from(
+					"Attempted to call `add_flush_task` on a `UniverseProxy` belonging to a dead universe.",
+				),
+			));
 			return;
 		};
 
-		proxy_state.flush_tasks.lock().push(task);
+		proxy_state.push_flush_task(task);
 	}
 }
 
@@ -389,6 +885,7 @@ impl<M: ?Sized> ArchetypeHandle<M> {
 		}
 	}
 
+	#[track_caller]
 	pub fn get_in_universe(self, universe: &Universe) -> MutexGuard<Archetype> {
 		universe.archetype_by_id(self.id())
 	}
@@ -449,6 +946,7 @@ impl<'r> ExclusiveUniverse<'r> {
 
 	// === Exclusive helpers === //
 
+	#[track_caller]
 	pub fn spawn_bundled<B: BuildableArchetype + Bundle>(
 		&mut self,
 		name: impl DebugLabel,
@@ -459,6 +957,7 @@ impl<'r> ExclusiveUniverse<'r> {
 			.spawn_with_universe(self, name, bundle)
 	}
 
+	#[track_caller]
 	pub fn spawn_bundled_single<T: 'static + Send + Sync>(
 		&mut self,
 		name: impl DebugLabel,
@@ -467,12 +966,14 @@ impl<'r> ExclusiveUniverse<'r> {
 		SingleEntity::new(self.spawn_bundled(name, SingleBundle(value)))
 	}
 
+	#[track_caller]
 	pub fn despawn_bundled<B: BuildableArchetype + Bundle>(&mut self, target: Entity) -> B {
 		self.universe_dangerous()
 			.archetype::<B>()
 			.despawn_and_extract_with_universe(self, target)
 	}
 
+	#[track_caller]
 	pub fn despawn_bundled_single<T: 'static + Send + Sync>(
 		&mut self,
 		target: SingleEntity<T>,
@@ -519,6 +1020,7 @@ impl<'r> ExclusiveUniverse<'r> {
 		self.universe_dangerous().resource_rw()
 	}
 
+	#[track_caller]
 	pub fn bypass_resource_ref<T>(&self) -> RwLockReadGuard<'r, T>
 	where
 		T: BuildableResourceRw + BypassExclusivity,
@@ -526,6 +1028,7 @@ impl<'r> ExclusiveUniverse<'r> {
 		self.universe_dangerous().resource_ref()
 	}
 
+	#[track_caller]
 	pub fn bypass_resource_mut<T>(&self) -> RwLockWriteGuard<'r, T>
 	where
 		T: BuildableResourceRw + BypassExclusivity,
@@ -533,6 +1036,7 @@ impl<'r> ExclusiveUniverse<'r> {
 		self.universe_dangerous().resource_mut()
 	}
 
+	#[track_caller]
 	pub fn bypass_storage<T>(&self) -> RwLockReadGuard<'r, Storage<T>>
 	where
 		T: 'static + Send + Sync + BypassExclusivity,
@@ -540,6 +1044,7 @@ impl<'r> ExclusiveUniverse<'r> {
 		self.universe_dangerous().storage()
 	}
 
+	#[track_caller]
 	pub fn bypass_storage_mut<T>(&self) -> RwLockWriteGuard<'r, Storage<T>>
 	where
 		T: 'static + Send + Sync + BypassExclusivity,
@@ -547,6 +1052,7 @@ impl<'r> ExclusiveUniverse<'r> {
 		self.universe_dangerous().storage_mut()
 	}
 
+	#[track_caller]
 	pub fn bypass_comp<T>(&self, target: Entity) -> MappedRwLockReadGuard<'r, T>
 	where
 		T: 'static + Send + Sync + BypassExclusivity,
@@ -554,6 +1060,7 @@ impl<'r> ExclusiveUniverse<'r> {
 		self.universe_dangerous().comp(target)
 	}
 
+	#[track_caller]
 	pub fn bypass_comp_mut<T>(&self, target: Entity) -> MappedRwLockWriteGuard<'r, T>
 	where
 		T: 'static + Send + Sync + BypassExclusivity,
@@ -565,6 +1072,7 @@ impl<'r> ExclusiveUniverse<'r> {
 		self.universe_dangerous().resource()
 	}
 
+	#[track_caller]
 	pub fn bypass_archetype<M>(&self) -> MappedMutexGuard<'r, Archetype<M>>
 	where
 		M: ?Sized + BuildableArchetype + BypassExclusivity,
@@ -572,6 +1080,7 @@ impl<'r> ExclusiveUniverse<'r> {
 		self.universe_dangerous().archetype()
 	}
 
+	#[track_caller]
 	pub fn bypass_archetype_meta<T: 'static + BypassExclusivity>(&self, id: ArchetypeId) -> &'r T {
 		self.universe_dangerous().archetype_meta(id)
 	}
@@ -594,3 +1103,96 @@ impl<T: ?Sized + BypassExclusivity> BypassExclusivity for RwLock<T> {}
 impl<T: BypassExclusivity> BypassExclusivity for Storage<T> {}
 
 impl<T: ?Sized + BypassExclusivity> BypassExclusivity for Archetype<T> {}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		debug::violation::ViolationKind,
+		testing::{capture_violations, expect_violation},
+	};
+
+	use super::*;
+
+	#[test]
+	fn borrow_conflicts_name_possible_holders() {
+		// Borrows are only tracked in debug builds.
+		if !cfg!(debug_assertions) {
+			return;
+		}
+
+		let universe = Universe::new();
+		let guard = universe.storage_mut::<u32>();
+		let line = line!() - 1;
+
+		let err = universe.try_storage::<u32>().unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			format!(
+				"{} is already mutably borrowed (possibly by the borrow at {}:{line})",
+				type_name::<Storage<u32>>(),
+				file!(),
+			),
+		);
+
+		drop(guard);
+	}
+
+	#[test]
+	fn lingering_tasks_are_reported_once() {
+		let linger = |universe: &mut Universe| {
+			universe.add_flush_task(UniverseFlushTask::new(|_| {}));
+			universe.proxied.flush_tasks.lock().since =
+				Some(Instant::now() - FLUSH_TASK_LINGER_WARN_THRESHOLD);
+		};
+
+		let mut universe = Universe::new();
+		linger(&mut universe);
+
+		let (_, violation) =
+			expect_violation(ViolationKind::UnflushedUniverse, || universe.flush());
+		assert!(!violation.to_string().contains("forget"), "{violation}");
+
+		// Dropping the universe only reports the tasks as leaked.
+		linger(&mut universe);
+
+		let (_, violations) = capture_violations(|| drop(universe));
+		assert_eq!(
+			violations
+				.iter()
+				.map(DebugViolation::kind)
+				.collect::<Vec<_>>(),
+			[ViolationKind::Leak],
+		);
+	}
+
+	#[test]
+	fn storage_leak_checks_are_deduplicated() {
+		let mut universe = Universe::new();
+		universe.resource_rw::<Storage<u32>>();
+		universe.unload_resource::<RwLock<Storage<u32>>>();
+		universe.resource_rw::<Storage<u32>>();
+
+		assert_eq!(universe.storage_leak_checks.lock().len(), 1);
+	}
+
+	#[test]
+	fn unflushed_tasks_are_reported() {
+		let mut universe = Universe::new();
+
+		let (_, violations) = capture_violations(|| {
+			for _ in 0..UNFLUSHED_WARN_THRESHOLD + 1 {
+				universe.add_flush_task(UniverseFlushTask::new(|_| {}));
+			}
+			universe.flush();
+		});
+
+		// We only warn once between flushes.
+		assert_eq!(
+			violations
+				.iter()
+				.map(DebugViolation::kind)
+				.collect::<Vec<_>>(),
+			[ViolationKind::UnflushedUniverse],
+		);
+	}
+}
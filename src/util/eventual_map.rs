@@ -1,6 +1,9 @@
 use std::{
+	any::Any,
 	borrow::Borrow,
+	cell::RefCell,
 	collections::HashMap,
+	fmt,
 	hash::{self, BuildHasher},
 	mem,
 	ops::{Index, IndexMut},
@@ -10,7 +13,73 @@ use std::{
 use derive_where::derive_where;
 use parking_lot::Mutex;
 
-use super::ptr::HeapPointerExt;
+use super::ptr::{addr_of_ptr, HeapPointerExt};
+
+// === Initialization Tracking === //
+
+thread_local! {
+	static INIT_STACK: RefCell<Vec<InitFrame>> = const { RefCell::new(Vec::new()) };
+}
+
+trait InitKey: Any + fmt::Debug {
+	fn as_any(&self) -> &dyn Any;
+}
+
+impl<K: Any + fmt::Debug> InitKey for K {
+	fn as_any(&self) -> &dyn Any {
+		self
+	}
+}
+
+struct InitFrame {
+	map: usize,
+	key: Box<dyn InitKey>,
+}
+
+struct InitFrameGuard;
+
+impl InitFrameGuard {
+	fn push<K: 'static + PartialEq + fmt::Debug>(map: usize, key: K) -> Self {
+		INIT_STACK.with(|stack| {
+			let mut stack = stack.borrow_mut();
+
+			// If this thread is already initializing this very key, calling into the `Once` would
+			// deadlock so we report the cycle instead.
+			let cycle_start = stack.iter().position(|frame| {
+				frame.map == map && (*frame.key).as_any().downcast_ref::<K>() == Some(&key)
+			});
+
+			if let Some(cycle_start) = cycle_start {
+				let chain = stack[cycle_start..]
+					.iter()
+					.map(|frame| format!("{:?}", frame.key))
+					.chain([format!("{key:?}")])
+					.collect::<Vec<_>>()
+					.join(" -> ");
+
+				drop(stack);
+				panic!("Detected a cycle while recursively initializing {key:?}: {chain}");
+			}
+
+			stack.push(InitFrame {
+				map,
+				key: Box::new(key),
+			});
+		});
+
+		Self
+	}
+}
+
+impl Drop for InitFrameGuard {
+	fn drop(&mut self) {
+		INIT_STACK.with(|stack| {
+			stack.borrow_mut().pop();
+		});
+	}
+}
+
+// === EventualMap === //
 
 #[derive(Debug)]
 #[derive_where(Default; S: Default)]
@@ -27,7 +96,7 @@ struct NurseryCell<V: ?Sized> {
 
 impl<K, V, S> EventualMap<K, V, S>
 where
-	K: hash::Hash + Eq + Copy,
+	K: 'static + hash::Hash + Eq + Copy + fmt::Debug,
 	V: ?Sized,
 	S: Default + BuildHasher,
 {
@@ -98,8 +167,17 @@ where
 				// not be the one actually invoking the method. This is because of the `nursery_map`
 				// mutex guard has been dropped so someone may have already observed our added entry
 				// and taken it upon themselves to initialize the cell instead of us.
+				//
+				// If this thread is already initializing this key further up the stack, this call
+				// would deadlock. We detect that case and panic with the offending cycle instead.
+				//
+				// That panic unwinds through the initializers of every other key in the cycle so we
+				// ignore poisoning. Otherwise, the cycle report would be followed by unrelated "poisoned
+				// `Once`" panics on every subsequent access to those keys. Instead, the next access
+				// simply retries the initialization.
+				let init_guard = InitFrameGuard::push(addr_of_ptr(self), key);
 				let mut value_to_return = None;
-				once.call_once(|| {
+				once.call_once_force(|_| {
 					// Oh good, nothing is locked right now so there's no chance of deadlock.
 					let value = f();
 
@@ -110,6 +188,7 @@ where
 					});
 					self.nursery.lock().get_mut(&key).unwrap().value = Some(value);
 				});
+				drop(init_guard);
 
 				// This was either just initialized in `call_once` or
 				value_to_return.unwrap_or_else(|| unsafe {
@@ -137,12 +216,38 @@ where
 		value
 	}
 
+	pub fn entries(&self) -> Vec<(K, &V)> {
+		let nursery_map = self.nursery.lock();
+
+		self.established
+			.iter()
+			.map(|(k, v)| (*k, &**v))
+			.chain(nursery_map.iter().filter_map(|(k, cell)| {
+				let value = cell.value.as_ref()?;
+
+				Some((*k, unsafe {
+					// Safety: the box is not going to be destroyed until the next mutating call to
+					// `EventualMap` and this box exhibits exterior mutability w.r.t. the container.
+					value.prolong_heap_ref()
+				}))
+			}))
+			.collect()
+	}
+
+	pub fn nursery_len(&self) -> usize {
+		self.nursery.lock().len()
+	}
+
+	pub fn keys(&self) -> Vec<K> {
+		self.entries().into_iter().map(|(k, _)| k).collect()
+	}
+
 	// === Synchronized === //
 
-	// pub fn insert(&mut self, key: K, value: Box<V>) -> Option<Box<V>> {
-	// 	self.flush();
-	// 	self.established.insert(key, value)
-	// }
+	pub fn insert(&mut self, key: K, value: Box<V>) -> Option<Box<V>> {
+		self.flush();
+		self.established.insert(key, value)
+	}
 
 	pub fn remove<Q>(&mut self, key: &Q) -> Option<Box<V>>
 	where
@@ -181,7 +286,7 @@ where
 
 impl<'a, K, V, S, Q> Index<&'a Q> for EventualMap<K, V, S>
 where
-	K: hash::Hash + Eq + Copy + Borrow<Q>,
+	K: 'static + hash::Hash + Eq + Copy + fmt::Debug + Borrow<Q>,
 	V: ?Sized,
 	S: Default + BuildHasher,
 	Q: ?Sized + Eq + hash::Hash,
@@ -195,7 +300,7 @@ where
 
 impl<'a, K, V, S, Q> IndexMut<&'a Q> for EventualMap<K, V, S>
 where
-	K: hash::Hash + Eq + Copy + Borrow<Q>,
+	K: 'static + hash::Hash + Eq + Copy + fmt::Debug + Borrow<Q>,
 	V: ?Sized,
 	S: Default + BuildHasher,
 	Q: ?Sized + Eq + hash::Hash,
@@ -204,3 +309,30 @@ where
 		self.get_mut(key).unwrap()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use std::panic::{catch_unwind, AssertUnwindSafe};
+
+	use fnv::FnvBuildHasher;
+
+	use super::*;
+
+	#[test]
+	fn cycles_do_not_poison() {
+		let map = EventualMap::<u32, u32, FnvBuildHasher>::default();
+
+		let err = catch_unwind(AssertUnwindSafe(|| {
+			map.get_or_create(1, || {
+				Box::new(*map.get_or_create(2, || Box::new(*map.get_or_create(1, || Box::new(3)))))
+			});
+		}))
+		.unwrap_err();
+
+		let message = err.downcast_ref::<String>().unwrap();
+		assert!(message.contains("1 -> 2 -> 1"), "{message}");
+
+		assert_eq!(*map.get_or_create(2, || Box::new(4)), 4);
+		assert_eq!(*map.get_or_create(1, || Box::new(5)), 5);
+	}
+}
@@ -12,10 +12,13 @@ fn slot_to_usize(v: u32) -> usize {
 pub struct FreeList<T> {
 	slots: Vec<Option<T>>,
 	free: hibitset::BitSet,
+	len: usize,
 }
 
 impl<T> FreeList<T> {
 	pub fn alloc(&mut self, value: T) -> u32 {
+		self.len += 1;
+
 		match (&self.free).iter().next() {
 			Some(slot) => {
 				self.free.remove(slot);
@@ -25,7 +28,6 @@ impl<T> FreeList<T> {
 			None => {
 				let slot = u32::try_from(self.slots.len()).unwrap();
 				self.slots.push(Some(value));
-				self.free.add(slot);
 				slot
 			}
 		}
@@ -33,7 +35,21 @@ impl<T> FreeList<T> {
 
 	pub fn dealloc(&mut self, slot: u32) -> Option<T> {
 		self.free.add(slot);
-		self.slots[slot_to_usize(slot)].take()
+		let value = self.slots[slot_to_usize(slot)].take();
+
+		if value.is_some() {
+			self.len -= 1;
+		}
+
+		value
+	}
+
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
 	}
 
 	pub fn get(&self, slot: u32) -> Option<&T> {
@@ -50,6 +66,13 @@ impl<T> FreeList<T> {
 		}
 	}
 
+	pub fn iter(&self) -> impl Iterator<Item = (u32, &T)> + '_ {
+		self.slots
+			.iter()
+			.enumerate()
+			.filter_map(|(slot, value)| Some((slot as u32, value.as_ref()?)))
+	}
+
 	// pub fn as_slice(&self) -> &[Option<T>] {
 	// 	&self.slots
 	// }
@@ -72,3 +95,29 @@ impl<T> IndexMut<u32> for FreeList<T> {
 		self.get_mut(slot).unwrap()
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn fresh_slots_are_not_reused() {
+		let mut list = FreeList::default();
+		let a = list.alloc("a");
+		let b = list.alloc("b");
+
+		assert_ne!(a, b);
+		assert_eq!(list[a], "a");
+		assert_eq!(list[b], "b");
+
+		// Both slots must be released exactly once.
+		assert_eq!(list.dealloc(a), Some("a"));
+		assert_eq!(list.dealloc(b), Some("b"));
+
+		let c = list.alloc("c");
+		let d = list.alloc("d");
+		assert_ne!(c, d);
+		assert_eq!(list[c], "c");
+		assert_eq!(list[d], "d");
+	}
+}